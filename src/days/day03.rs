@@ -21,11 +21,11 @@ fn largest_voltage<const SIZE: usize>(bank: &[u8]) -> u64 {
         if top[0] <= b {
             let mut carry = top[0];
             top[0] = b;
-            for i in 1..SIZE {
-                if carry < top[i] {
+            for slot in top.iter_mut().skip(1) {
+                if carry < *slot {
                     break;
                 }
-                (top[i], carry) = (carry, top[i]);
+                (*slot, carry) = (carry, *slot);
             }
         }
     }
@@ -1,4 +1,4 @@
-use crate::BLANK_LINE;
+use crate::util::section::SectionOps as _;
 
 pub const INPUT: &str = include_str!("../inputs/05/real.txt");
 
@@ -26,7 +26,7 @@ pub fn solve(input: &str) -> (usize, u64) {
 
 #[inline]
 fn parse_input(input: &str) -> (Vec<Range>, Vec<u64>) {
-    let (ranges, ingredients) = input.split_once(BLANK_LINE).unwrap();
+    let [ranges, ingredients] = input.sections().exactly().unwrap_or_else(|e| panic!("{e}"));
     let mut ranges = ranges
         .lines()
         .map(|l| {
@@ -1,3 +1,5 @@
+use crate::util::grid::Grid;
+
 pub const INPUT: &str = include_str!("../inputs/06/real.txt");
 
 pub fn solve(input: &str) -> (u64, u64) {
@@ -61,34 +63,31 @@ impl<const N: usize> Default for Column<N> {
 }
 
 fn part2<const N: usize>(input: &str) -> u64 {
-    let grid = input.lines().map(str::as_bytes).collect::<Vec<_>>();
-    let operator_line = grid[N];
-    let grid_width = operator_line.len() - 1;
+    // Numbers are right aligned, so trimmed trailing spaces would shift the columns.
+    let grid = Grid::parse_padded(input, b' ').expect("Error in input");
 
     let mut p2 = 0;
     let mut column_numbers = Vec::new();
-    let mut x_iter = (0..=grid_width).rev();
-
-    while let Some(x) = x_iter.next() {
-        let mut num = 0_u64;
-        for y in 0..N {
-            let byte = grid[y][x];
-            if byte.is_ascii_digit() {
-                num = num * 10 + (byte - b'0') as u64;
-            }
-        }
+    let mut columns = grid.columns().rev();
+
+    while let Some(mut column) = columns.next() {
+        let num = column
+            .by_ref()
+            .take(N)
+            .filter(|byte| byte.is_ascii_digit())
+            .fold(0_u64, |num, &byte| num * 10 + (byte - b'0') as u64);
         column_numbers.push(num);
 
-        match operator_line[x] {
-            b'+' => {
+        match column.next() {
+            Some(b'+') => {
                 p2 += column_numbers.iter().sum::<u64>();
                 column_numbers.clear();
-                x_iter.next(); // Skip blank separator
+                columns.next(); // Skip blank separator
             }
-            b'*' => {
+            Some(b'*') => {
                 p2 += column_numbers.iter().product::<u64>();
                 column_numbers.clear();
-                x_iter.next(); // Skip blank separator
+                columns.next(); // Skip blank separator
             }
             _ => {}
         }
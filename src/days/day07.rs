@@ -1,11 +1,19 @@
-use crate::util::grid::Grid;
+use crate::util::grid::{Grid, cells};
 use crate::util::hash::{FastMap, FastMapBuilder as _, FastSet, FastSetBuilder as _};
 use crate::util::point::Point;
 
 pub const INPUT: &str = include_str!("../inputs/07/real.txt");
 
+cells! {
+    enum Tile {
+        Empty = b'.',
+        Splitter = b'^',
+        Start = b'S',
+    }
+}
+
 pub fn solve(input: &str) -> (usize, u64) {
-    let grid = Grid::parse(input);
+    let grid = Grid::parse_cells(input).unwrap_or_else(|e| panic!("Error in input: {e}"));
     let mut splits: FastSet<Point> = FastSet::new();
     let mut cache: FastMap<Point, u64> = FastMap::new();
     
@@ -18,7 +26,7 @@ pub fn solve(input: &str) -> (usize, u64) {
 }
 
 fn dfs(
-    grid: &Grid<u8>,
+    grid: &Grid<Tile>,
     start: Point,
     splitters: &mut FastSet<Point>,
     cache: &mut FastMap<Point, u64>,
@@ -35,8 +43,8 @@ fn dfs(
                 cache.insert(start, 1);
                 return 1;
             }
-            Some(b'.') => beam.y += 1,
-            Some(b'^') => {
+            Some(Tile::Empty | Tile::Start) => beam.y += 1,
+            Some(Tile::Splitter) => {
                 splitters.insert(beam);
                 let left = Point { x: beam.x - 1, y: beam.y };
                 let right = Point { x: beam.x + 1, y: beam.y };
@@ -47,18 +55,12 @@ fn dfs(
                 cache.insert(start, paths);
                 return paths;
             }
-            _ => unreachable!("Error in code")
         }
     }
 }
 
-fn find_start(grid: &Grid<u8>) -> Point {
-    for x in 0..grid.width {
-        if grid.bytes[x as usize] == b'S' {
-            return Point { x, y: 0 };
-        }
-    }
-    panic!("Error in input")
+fn find_start(grid: &Grid<Tile>) -> Point {
+    grid.find(Tile::Start).expect("Error in input")
 }
 
 #[cfg(test)]
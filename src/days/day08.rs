@@ -18,25 +18,54 @@
 
 use crate::util::iter::ChunkOps as _;
 use crate::util::parse::ParseOps as _;
+use crate::util::point3::Point3;
 
 pub const INPUT: &str = include_str!("../inputs/08/real.txt");
 
-type Point = [usize; 3];
+type Point = Point3<i64>;
 type Pair = (u16, u16, usize);
 
 const BUCKETS: usize = 5;
 const BUCKET_SIZE: usize = 100_000_000; // 10_000^2
+const MAX_POINTS: usize = u16::MAX as usize + 1;
 
 pub fn solve(input: &str) -> (usize, usize) {
-    let points: Vec<Point> = input.iter_unsigned().chunk::<3>().collect();
+    let points: Vec<Point> = input.iter_signed().chunk::<3>().map(Point3::from).collect();
+    let buckets = buckets(&points);
+    let (p1, p2) = run::<1000>(&points, &buckets);
+    (p1, p2)
+}
+
+/// Reports inputs that break the assumptions above: point indices must fit in `u16`, and pairs
+/// should spread over the buckets rather than all landing in the last one.
+pub fn check(input: &str) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let numbers = input.iter_signed::<i64>().count();
+    if numbers % 3 != 0 {
+        warnings.push(format!("{numbers} numbers is not a whole number of 3D points"));
+    }
+
+    let points: Vec<Point> = input.iter_signed().chunk::<3>().map(Point3::from).collect();
+    if points.len() > MAX_POINTS {
+        warnings.push(format!("{} points don't fit in u16 pair indices", points.len()));
+        return warnings;
+    }
+
+    let buckets = buckets(&points);
+    if buckets[..BUCKETS - 1].iter().all(Vec::is_empty) && !buckets[BUCKETS - 1].is_empty() {
+        warnings.push(format!("All pairs are further apart than {BUCKETS} buckets of 10_000²"));
+    }
+
+    warnings
+}
+
+/// Every pair of points, bucketed by distance and sorted within each bucket.
+fn buckets(points: &[Point]) -> Vec<Vec<Pair>> {
     let mut buckets: Vec<Vec<Pair>> = vec![vec![]; BUCKETS];
 
     for (i, &p1) in points.iter().enumerate() {
         for (j, &p2) in points.iter().enumerate().skip(i + 1) {
-            let dx = p1[0].abs_diff(p2[0]);
-            let dy = p1[1].abs_diff(p2[1]);
-            let dz = p1[2].abs_diff(p2[2]);
-            let dist = dx * dx + dy * dy + dz * dz;
+            let dist = p1.euclidean_squared(p2) as usize;
             let bucket = (dist / BUCKET_SIZE).min(BUCKETS - 1);
             buckets[bucket].push((i as u16, j as u16, dist));
         }
@@ -47,8 +76,7 @@ pub fn solve(input: &str) -> (usize, usize) {
         bucket.sort_unstable_by_key(|&(.., d)| d);
     }
 
-    let (p1, p2) = run::<1000>(&points, &buckets);
-    (p1, p2)
+    buckets
 }
 
 fn run<const PAIRS: usize>(points: &[Point], buckets: &[Vec<Pair>]) -> (usize, usize) {
@@ -72,7 +100,7 @@ fn run<const PAIRS: usize>(points: &[Point], buckets: &[Vec<Pair>]) -> (usize, u
         }
 
         if merged_size == n {
-            return (p1, points[i][0] * points[j][0]);
+            return (p1, (points[i].x * points[j].x) as usize);
         }
     }
 
@@ -108,24 +136,8 @@ mod tests {
 
     #[test]
     fn test_with_example() {
-        let points: Vec<Point> = TEST.iter_unsigned().chunk::<3>().collect();
-        let mut buckets: Vec<Vec<Pair>> = vec![vec![]; BUCKETS];
-
-        for (i, &p1) in points.iter().enumerate() {
-            for (j, &p2) in points.iter().enumerate().skip(i + 1) {
-                let dx = p1[0].abs_diff(p2[0]);
-                let dy = p1[1].abs_diff(p2[1]);
-                let dz = p1[2].abs_diff(p2[2]);
-                let dist = dx * dx + dy * dy + dz * dz;
-                let bucket = (dist / BUCKET_SIZE).min(BUCKETS - 1);
-                buckets[bucket].push((i as u16, j as u16, dist));
-            }
-        }
-        for bucket in &mut buckets {
-            bucket.sort_unstable_by_key(|&(.., d)| d);
-        }
-
-        let (p1, p2) = run::<10>(&points, &buckets);
+        let points: Vec<Point> = TEST.iter_signed().chunk::<3>().map(Point3::from).collect();
+        let (p1, p2) = run::<10>(&points, &buckets(&points));
         assert_eq!(p1, 40);
         assert_eq!(p2, 25272);
     }
@@ -1,27 +1,23 @@
-use crate::util::grid::Grid;
-use crate::util::hash::*;
+use crate::util::bitgrid::BitGrid;
+use crate::util::compress::CompressedGrid;
 use crate::util::point::*;
+use crate::util::prefix::SummedAreaTable;
+use crate::util::rect::Rect;
 
 use itertools::Itertools as _;
-use std::collections::VecDeque;
 
 pub const INPUT: &str = include_str!("../inputs/09/real.txt");
-const UNKNOWN: i64 = 2;
-const INSIDE: i64 = 1;
-const OUTSIDE: i64 = 0;
 
-struct Point2D(u64, u64);
+type Tile = Point<i64>;
 
-impl Point2D {
-    fn parse(line: &str) -> Point2D {
-        let (x, y) = line.split_once(',').unwrap();
+fn parse(line: &str) -> Tile {
+    let (x, y) = line.split_once(',').unwrap();
 
-        Point2D(x.parse().unwrap(), y.parse().unwrap())
-    }
+    Point::new(x.parse().unwrap(), y.parse().unwrap())
 }
 
 pub fn solve(input: &str) -> (u64, u64) {
-    let points = input.lines().map(Point2D::parse).collect::<Vec<_>>();
+    let points = input.lines().map(parse).collect::<Vec<_>>();
 
     let p1 = p1(&points);
     let p2 = p2(&points);
@@ -29,120 +25,63 @@ pub fn solve(input: &str) -> (u64, u64) {
     (p1, p2)
 }
 
-fn p1(points: &[Point2D]) -> u64 {
-    points
-        .iter()
-        .tuple_combinations()
-        .map(|(a, b)| {
-            let w = u64::abs_diff(a.0, b.0) + 1;
-            let h = u64::abs_diff(a.1, b.1) + 1;
-
-            w * h
-        })
-        .max()
-        .unwrap()
+fn p1(points: &[Tile]) -> u64 {
+    points.iter().tuple_combinations().map(|(&a, &b)| area(a, b)).max().unwrap()
 }
 
-// 1. Compress: Add sentinel values (MIN/MAX) to coordinates, sort, dedup, create index mapping
+// 1. Compress: Map coordinates to compressed cells, with an empty border around the polygon
 // 2. Shrink points: Map original coordinates to compressed indices
-// 3. Mark boundary: Draw polygon edges on compressed grid as INSIDE
-// 4. Flood fill: From (0,0) mark all reachable cells as OUTSIDE
+// 3. Mark boundary: Draw polygon edges on a compressed bit grid
+// 4. Flood fill: From (0,0) mark all reachable cells as outside, everything else is inside
 // 5. Prefix sums: Build 2D prefix sum of "inside" cells
 // 6. Check pairs: For each vertex pair, use prefix sum to verify all cells in rectangle are inside, compute real area if valid
-fn p2(points: &[Point2D]) -> u64 {
+fn p2(points: &[Tile]) -> u64 {
     let size = points.len();
-    let (xs, ys) = compress(points);
-
-    let shrunk: Vec<_> = points.iter().map(|p| (xs[&p.0], ys[&p.1])).collect();
-    let mut grid = build_grid(size, xs, ys, &shrunk);
+    let compressed = CompressedGrid::from_points(points.iter().map(|p| (p.x, p.y)));
 
-    for y in 1..grid.height {
-        for x in 1..grid.width {
-            let point = Point::new(x, y);
-            let value = i64::from(grid[point] != OUTSIDE);
-            grid[point] = value + grid[point + UP] + grid[point + LEFT] - grid[point + UP + LEFT];
-        }
-    }
+    let shrunk: Vec<_> = points.iter().map(|p| compressed.point((p.x, p.y))).collect();
+    let grid = build_grid(BitGrid::new(compressed.xs.len(), compressed.ys.len()), &shrunk);
+    let inside = SummedAreaTable::from_bits(&grid);
 
-    let mut area = 0;
+    let mut best = 0;
     for i in 0..size {
         for j in i + 1..size {
-            let (x1, y1, x2, y2) = minmax(shrunk[i], shrunk[j]);
-
-            let expected = (x2 - x1 + 1) as i64 * (y2 - y1 + 1) as i64;
+            let rect = Rect::from_corners(shrunk[i], shrunk[j]);
 
-            let actual = grid[Point::new(x2, y2)]
-                - grid[Point::new(x1 - 1, y2)]
-                - grid[Point::new(x2, y1 - 1)]
-                + grid[Point::new(x1 - 1, y1 - 1)];
+            let expected = rect.area() as i64;
+            let actual = inside.sum(rect.min, rect.max);
 
             if expected == actual {
-                let Point2D(px1, py1) = points[i];
-                let Point2D(px2, py2) = points[j];
-                let dx = px1.abs_diff(px2) + 1;
-                let dy = py1.abs_diff(py2) + 1;
-                area = area.max(dx * dy);
+                best = best.max(area(points[i], points[j]));
             }
         }
     }
 
-    area
+    best
 }
 
-fn build_grid(
-    size: usize,
-    xs: FastMap<u64, i32>,
-    ys: FastMap<u64, i32>,
-    shrunk: &Vec<(i32, i32)>,
-) -> Grid<i64> {
-    let mut grid = Grid::new(xs.len() as i32, ys.len() as i32, UNKNOWN);
+fn build_grid(mut grid: BitGrid, shrunk: &[Point]) -> BitGrid {
+    let size = shrunk.len();
 
     // Draw edges between consecutive vertices (polygon boundary)
     for i in 0..size {
-        let (x1, y1, x2, y2) = minmax(shrunk[i], shrunk[(i + 1) % size]);
-        for x in x1..=x2 {
-            for y in y1..=y2 {
-                grid[Point::new(x, y)] = INSIDE;
-            }
+        for p in Rect::from_corners(shrunk[i], shrunk[(i + 1) % size]).points() {
+            grid.set(p, true);
         }
     }
 
-    // Step 3: Flood fill from origin (0,0) to mark OUTSIDE
-    let mut todo = VecDeque::from([ORIGIN]);
-    grid[ORIGIN] = OUTSIDE;
-
-    while let Some(point) = todo.pop_front() {
-        for next in ORTHOGONAL.map(|o| point + o) {
-            if grid.contains(next) && grid[next] == UNKNOWN {
-                grid[next] = OUTSIDE;
-                todo.push_back(next);
-            }
-        }
-    }
-    grid
-}
-
-fn compress(points: &[Point2D]) -> (FastMap<u64, i32>, FastMap<u64, i32>) {
-    let mut xs: Vec<_> = points.iter().map(|points| points.0).collect();
-    xs.push(u64::MIN);
-    xs.push(u64::MAX);
-    xs.sort_unstable();
-    xs.dedup();
-    let xs = xs.iter().enumerate().map(|(i, &n)| (n, i as i32)).collect();
-
-    let mut ys: Vec<_> = points.iter().map(|points| points.1).collect();
-    ys.push(u64::MIN);
-    ys.push(u64::MAX);
-    ys.sort_unstable();
-    ys.dedup();
-    let ys = ys.iter().enumerate().map(|(i, &n)| (n, i as i32)).collect();
-
-    (xs, ys)
+    // Step 3: Flood fill from origin (0,0), then flip so only the outside is clear
+    let mut inside = grid.clone();
+    inside.flood_fill(ORIGIN);
+    inside ^= &grid;
+    inside.invert();
+    inside
 }
 
+/// Area of the rectangle with opposite corners `a` and `b`, both inclusive.
 #[inline]
-fn minmax((x1, y1): (i32, i32), (x2, y2): (i32, i32)) -> (i32, i32, i32, i32) {
-    (x1.min(x2), y1.min(y2), x1.max(x2), y1.max(y2))
+fn area(a: Tile, b: Tile) -> u64 {
+    Rect::from_corners(a, b).area() as u64
 }
 
 #[cfg(test)]
@@ -43,6 +43,13 @@ use crate::util::parse::*;
 
 pub const INPUT: &str = include_str!("../inputs/10/real.txt");
 
+/// Lights and joltages are stored in `u16` masks and `[u16; 16]` arrays.
+const MAX_LIGHTS: usize = 16;
+/// Button combinations are grouped in a table indexed by XOR pattern.
+const MAX_PATTERN: usize = 1024;
+/// All 2^n button combinations are precomputed for each machine.
+const MAX_BUTTONS: usize = 10;
+
 pub fn solve(input: &str) -> (usize, u64) {
     let mut p1 = 0;
     let mut p2 = 0;
@@ -50,7 +57,8 @@ pub fn solve(input: &str) -> (usize, u64) {
     // Reusable buffers
     let mut buttons = Vec::with_capacity(16);
     let mut button_diffs = Vec::with_capacity(16);
-    let mut mask_to_combos: [Vec<(u32, [u16; 16])>; 1024] = std::array::from_fn(|_| Vec::new());
+    let mut mask_to_combos: [Vec<(u32, [u16; 16])>; MAX_PATTERN] =
+        std::array::from_fn(|_| Vec::new());
     let mut cache = FastMap::new();
 
     for line in input.lines() {
@@ -63,7 +71,7 @@ pub fn solve(input: &str) -> (usize, u64) {
         for part in line.split_whitespace() {
             match part.as_bytes()[0] {
                 b'[' => {
-                    for (i, &b) in part[1..part.len()-1].as_bytes().iter().enumerate() {
+                    for (i, &b) in part.as_bytes()[1..part.len() - 1].iter().enumerate() {
                         if b == b'#' {
                             lights |= 1 << i;
                         }
@@ -107,11 +115,52 @@ pub fn solve(input: &str) -> (usize, u64) {
     (p1, p2)
 }
 
+/// Reports machines with more lights, patterns or buttons than the fixed size tables allow.
+pub fn check(input: &str) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    for (y, line) in input.lines().enumerate() {
+        let mut lights = 0;
+        let mut buttons = 0;
+        let mut joltages = 0;
+        let mut indices = 0;
+
+        for part in line.split_whitespace() {
+            match part.as_bytes()[0] {
+                b'[' => lights = part.len().saturating_sub(2),
+                b'(' => {
+                    buttons += 1;
+                    let highest = part.iter_unsigned::<usize>().max();
+                    indices = indices.max(highest.map_or(0, |i| i + 1));
+                }
+                b'{' => joltages = part.iter_unsigned::<u64>().count(),
+                _ => {}
+            }
+        }
+
+        let line = y + 1;
+        let positions = lights.max(joltages).max(indices);
+        if positions > MAX_LIGHTS {
+            warnings.push(format!("Line {line} has {positions} positions, limit is {MAX_LIGHTS}"));
+        }
+        // Patterns are indexed by every position, including joltage counters and button indices.
+        if positions > MAX_PATTERN.trailing_zeros() as usize {
+            let table = MAX_PATTERN;
+            warnings.push(format!("Line {line} has 2^{positions} patterns, table holds {table}"));
+        }
+        if buttons > MAX_BUTTONS {
+            warnings.push(format!("Line {line} has {buttons} buttons, limit is {MAX_BUTTONS}"));
+        }
+    }
+
+    warnings
+}
+
 /// Precompute all 2^n button combinations, storing (count, diff) grouped by XOR mask
 fn precompute_combos(
     buttons: &[u16],
     button_diffs: &[[u16; 16]],
-    mask_to_combos: &mut [Vec<(u32, [u16; 16])>; 1024],
+    mask_to_combos: &mut [Vec<(u32, [u16; 16])>; MAX_PATTERN],
 ) {
     let n = buttons.len();
     for combo in 0..(1_u32 << n) {
@@ -134,7 +183,7 @@ fn precompute_combos(
 }
 
 fn dfs(
-    mask_to_combos: &[Vec<(u32, [u16; 16])>; 1024],
+    mask_to_combos: &[Vec<(u32, [u16; 16])>; MAX_PATTERN],
     cache: &mut FastMap<[u16; 16], u64>,
     joltages: [u16; 16],
 ) -> u64 {
@@ -147,8 +196,8 @@ fn dfs(
     }
 
     let mut pattern = 0_usize;
-    for i in 0..16 {
-        pattern |= (joltages[i] as usize & 1) << i;
+    for (i, &joltage) in joltages.iter().enumerate() {
+        pattern |= (joltage as usize & 1) << i;
     }
 
     const INF: u64 = 1_000_000;
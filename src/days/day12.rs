@@ -1,13 +1,12 @@
-use crate::{
-    BLANK_LINE,
-    util::{iter::ChunkOps as _, parse::ParseOps as _},
-};
+use crate::util::iter::ChunkOps as _;
+use crate::util::parse::ParseOps as _;
+use crate::util::section::{Section, SectionOps as _};
 
 pub const INPUT: &str = include_str!("../inputs/12/real.txt");
 
 pub fn solve(input: &str) -> (i32, i32) {
     let mut p1 = 0;
-    let parts = input.split(BLANK_LINE).collect::<Vec<_>>();
+    let parts = input.sections().collect::<Vec<_>>();
     if let Some((regions, shapes)) = parts.split_last() {
         let shapes = shapes.iter().map(shape_size).collect::<Vec<_>>();
 
@@ -18,7 +17,7 @@ pub fn solve(input: &str) -> (i32, i32) {
                 .iter()
                 .skip(2)
                 .enumerate()
-                .map(|(shape, count)| count * &shapes[shape])
+                .map(|(shape, count)| count * shapes[shape])
                 .sum::<usize>();
 
             if actual < size {
@@ -30,10 +29,9 @@ pub fn solve(input: &str) -> (i32, i32) {
     (p1, 0)
 }
 
-fn shape_size(shape: &&str) -> usize {
+fn shape_size(shape: &Section<'_>) -> usize {
     shape
         .lines()
-        .skip(1)
         .flat_map(|l| l.bytes())
         .filter(|&b| b == b'#')
         .count()
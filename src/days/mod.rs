@@ -7,6 +7,9 @@ use std::time::Instant;
 /// - `pub fn solve(input: &str) -> (A, B)` where A, B: Display
 ///
 /// Adding a new day = add one identifier to this list.
+///
+/// Also generates `DAYS`, the list of solved days, and `input`, which returns the puzzle input
+/// of a day for `inspect`.
 macro_rules! days {
     ($($day:ident),* $(,)?) => {
         $(mod $day;)*
@@ -26,6 +29,16 @@ macro_rules! days {
                 _ => 0.0,
             }
         }
+
+        /// Every day with a solution, in order.
+        pub const DAYS: &[u8] = &[$(days!(@num $day)),*];
+
+        pub fn input(day: u8) -> Option<&'static str> {
+            match day {
+                $(days!(@num $day) => Some($day::INPUT),)*
+                _ => None,
+            }
+        }
     };
     // Helper to extract day number from identifier like day01 -> 1
     (@num day01) => { 1 };
@@ -57,4 +70,12 @@ days!(
     day12,
 );
 
-
+/// Warnings for inputs that break assumptions a day's solution is built on, such as fixed
+/// table sizes or index widths.
+pub fn check(day: u8, input: &str) -> Vec<String> {
+    match day {
+        8 => day08::check(input),
+        10 => day10::check(input),
+        _ => Vec::new(),
+    }
+}
@@ -0,0 +1,188 @@
+//! Summarizes a puzzle input without solving it.
+//!
+//! Useful to check the shape of a new input before writing a parser, or to spot values that would
+//! overflow the fixed size types a solution was written with. Run with:
+//!
+//! ```none
+//!   cargo run --release inspect <day>
+//! ```
+use crate::days;
+use crate::util::grid::Grid;
+use crate::util::section::SectionOps as _;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+/// Maximum number of distinct line lengths listed before the histogram is truncated.
+const MAX_LENGTHS: usize = 8;
+
+pub fn inspect_day(day: u8) {
+    let Some(input) = days::input(day) else {
+        println!("No input for day {day}");
+        return;
+    };
+
+    println!("\n=== Inspect Day {day:02} ===");
+    print!("{}", summarize(input));
+    for warning in days::check(day, input) {
+        println!("  · Warning: {warning}");
+    }
+}
+
+pub fn summarize(input: &str) -> String {
+    let mut out = String::new();
+
+    let lines: Vec<_> = input.lines().collect();
+    let blank = lines.iter().filter(|l| l.trim().is_empty()).count();
+    let _ = writeln!(out, "  · Lines: {} ({blank} blank)", lines.len());
+
+    let mut lengths = BTreeMap::new();
+    for line in &lines {
+        *lengths.entry(line.len()).or_insert(0_usize) += 1;
+    }
+    let mut histogram: Vec<_> =
+        lengths.iter().take(MAX_LENGTHS).map(|(len, count)| format!("{len} ×{count}")).collect();
+    if lengths.len() > MAX_LENGTHS {
+        histogram.push(format!("… {} more", lengths.len() - MAX_LENGTHS));
+    }
+    let _ = writeln!(out, "  · Line lengths: {}", histogram.join(", "));
+
+    match Grid::try_parse(input) {
+        Ok(grid) => {
+            let _ = writeln!(out, "  · Grid: {} × {}", grid.width, grid.height);
+        }
+        Err(e) => {
+            let _ = writeln!(out, "  · Grid: not rectangular ({e})");
+        }
+    }
+
+    let numbers = numbers(input);
+    match range(numbers.iter().map(|&(_, n)| n)) {
+        (0, _) => {}
+        (count, Some((min, max))) => {
+            let _ = writeln!(out, "  · Unsigned: {count} numbers in {min}..={max}");
+        }
+        (count, None) => {
+            let _ = writeln!(out, "  · Unsigned: {count} numbers, some exceed u128");
+        }
+    }
+    if numbers.iter().any(|&(negative, _)| negative) {
+        let signed = numbers.iter().map(|&(negative, n)| {
+            let n = i128::try_from(n?).ok()?;
+            Some(if negative { -n } else { n })
+        });
+        match range(signed) {
+            (count, Some((min, max))) => {
+                let _ = writeln!(out, "  · Signed: {count} numbers in {min}..={max}");
+            }
+            (count, _) => {
+                let _ = writeln!(out, "  · Signed: {count} numbers, some exceed i128");
+            }
+        }
+    }
+
+    let mut counts = [0_usize; 256];
+    for b in input.bytes() {
+        counts[b as usize] += 1;
+    }
+    let chars: Vec<_> = (0..=255_u8)
+        .filter(|&b| counts[b as usize] > 0 && b != b'\n' && b != b'\r')
+        .map(|b| format!("{:?} ×{}", b as char, counts[b as usize]))
+        .collect();
+    let _ = writeln!(out, "  · Characters: {}", chars.join(", "));
+
+    let sections: Vec<_> = input
+        .sections()
+        .map(|s| {
+            let name = s.name.map(|name| format!("\"{name}\" ")).unwrap_or_default();
+            format!("{name}line {} ({} lines)", s.line, s.lines().count())
+        })
+        .collect();
+    if sections.len() > 1 {
+        let _ = writeln!(out, "  · Sections: {}", sections.len());
+        for section in sections {
+            let _ = writeln!(out, "      {section}");
+        }
+    }
+
+    out
+}
+
+/// Every run of decimal digits, as whether a `-` comes right before it and its value, or `None`
+/// if it doesn't fit in `u128`. Overflow is checked, as inputs often have digit strings far
+/// longer than any integer type.
+fn numbers(input: &str) -> Vec<(bool, Option<u128>)> {
+    let bytes = input.as_bytes();
+    let mut numbers = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let negative = i > 0 && bytes[i - 1] == b'-';
+        let mut n = Some(0_u128);
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            let digit = (bytes[i] - b'0') as u128;
+            n = n.and_then(|n| n.checked_mul(10)?.checked_add(digit));
+            i += 1;
+        }
+        numbers.push((negative, n));
+    }
+
+    numbers
+}
+
+/// Number of values and their range, or `None` for the range if any value didn't fit.
+fn range<T: Copy + Ord>(iter: impl Iterator<Item = Option<T>>) -> (usize, Option<(T, T)>) {
+    let mut count = 0;
+    let mut fits = true;
+    let mut bounds = None;
+
+    for n in iter {
+        count += 1;
+        match n {
+            Some(n) => {
+                let (min, max) = bounds.unwrap_or((n, n));
+                bounds = Some((min.min(n), max.max(n)));
+            }
+            None => fits = false,
+        }
+    }
+
+    (count, bounds.filter(|_| fits))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary() {
+        let summary = summarize("0:\n#.\n.#\n\n1:\n-3,10\n");
+        let expected = [
+            "  · Lines: 6 (1 blank)",
+            "  · Line lengths: 0 ×1, 2 ×4, 5 ×1",
+            "  · Grid: not rectangular (Line 4 has 0 columns, expected 2)",
+            "  · Unsigned: 4 numbers in 0..=10",
+            "  · Signed: 4 numbers in -3..=10",
+            "  · Characters: '#' ×2, ',' ×1, '-' ×1, '.' ×2, '0' ×2, '1' ×2, '3' ×1, ':' ×2",
+            "  · Sections: 2",
+            "      \"0\" line 1 (2 lines)",
+            "      \"1\" line 5 (1 lines)",
+        ];
+        assert_eq!(summary.lines().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn long_numbers() {
+        let digits = "9".repeat(45);
+        let summary = summarize(&format!("{digits}\n{digits}\n"));
+        assert!(summary.contains("  · Unsigned: 2 numbers, some exceed u128\n"));
+        assert!(!summary.contains("Signed"));
+
+        let summary = summarize(&format!("-{digits} 3\n"));
+        assert!(summary.contains("  · Signed: 2 numbers, some exceed i128\n"));
+    }
+}
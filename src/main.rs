@@ -1,16 +1,20 @@
 mod days;
+mod inspect;
 mod util;
 
 use std::env;
 
-#[cfg(unix)]
-const BLANK_LINE: &str = "\n\n";
-
-#[cfg(windows)]
-const BLANK_LINE: &str = "\r\n\r\n";
-
 fn main() {
     let args = env::args().collect::<Vec<_>>();
+
+    if args.get(1).is_some_and(|arg| arg == "inspect") {
+        let days = if args.len() > 2 { parse_days(&args[2..]) } else { days::DAYS.to_vec() };
+        for day in days {
+            inspect::inspect_day(day);
+        }
+        return;
+    }
+
     println!("Advent of Code 2025 Solutions");
     println!("=============================\n");
     if args.len() > 1 {
@@ -19,17 +23,7 @@ fn main() {
         println!("Running all days");
     }
 
-    let days: Vec<u8> = if args.len() > 1 {
-        args[1..]
-            .iter()
-            .map(|x| {
-                x.parse()
-                    .unwrap_or_else(|v| panic!("Not a valid day: {}", v))
-            })
-            .collect()
-    } else {
-        (1..=25).collect()
-    };
+    let days = parse_days(&args[1..]);
 
     let mut runtime = 0.0;
 
@@ -40,3 +34,15 @@ fn main() {
     println!("Total runtime: {:.4} ms", runtime);
 }
 
+fn parse_days(args: &[String]) -> Vec<u8> {
+    if args.is_empty() {
+        (1..=25).collect()
+    } else {
+        args.iter()
+            .map(|x| {
+                x.parse()
+                    .unwrap_or_else(|v| panic!("Not a valid day: {}", v))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,287 @@
+//! Engine for [cellular automata] on a [`Grid`] or [`SparseGrid`].
+//!
+//! A rule maps the current state of a cell and the number of its "live" neighbors to a new
+//! state. Which neighbors are considered is given by a list of offsets, usually
+//! [`ORTHOGONAL`] or [`DIAGONAL`], and which neighbor states count as live by a predicate.
+//!
+//! For a [`Grid`] every cell has a state of type `T`. For a [`SparseGrid`] the state is
+//! `Option<T>`, where `None` is an empty cell, so rules can both remove cells and create new ones
+//! next to existing cells.
+//!
+//! Two update strategies are provided:
+//! * [`run`] applies the rule to every cell at once, generation after generation, until the
+//!   state stops changing or repeats an earlier generation.
+//! * [`settle`] only re-evaluates cells next to a cell that changed in the previous wave. This is
+//!   much faster when changes are local, for example removing cells until a stable shape is left.
+//!
+//! Both return the number of changed and live cells for each generation.
+//!
+//! [cellular automata]: https://en.wikipedia.org/wiki/Cellular_automaton
+use crate::util::grid::Grid;
+use crate::util::hash::*;
+use crate::util::point::*;
+use crate::util::sparse::SparseGrid;
+use std::hash::{BuildHasher as _, Hash};
+
+/// Storage that an automaton can run on.
+pub trait Space: Clone {
+    type Cell: Clone + PartialEq;
+
+    /// State at `p`, or `None` if the point is outside the space.
+    fn read(&self, p: Point) -> Option<Self::Cell>;
+    fn write(&mut self, p: Point, cell: Self::Cell);
+    /// Every point whose state could change in the next generation.
+    fn candidates(&self, offsets: &[Point]) -> Vec<Point>;
+    /// Hash of the whole state, used to find possible cycles.
+    fn fingerprint(&self) -> u64;
+    /// Whether both hold the same state, used to confirm a cycle when fingerprints match.
+    fn same(&self, other: &Self) -> bool;
+}
+
+impl<T: Clone + PartialEq + Hash> Space for Grid<T> {
+    type Cell = T;
+
+    #[inline]
+    fn read(&self, p: Point) -> Option<T> {
+        self.get(p).cloned()
+    }
+
+    #[inline]
+    fn write(&mut self, p: Point, cell: T) {
+        self[p] = cell;
+    }
+
+    fn candidates(&self, _: &[Point]) -> Vec<Point> {
+        (0..self.height).flat_map(|y| (0..self.width).map(move |x| Point::new(x, y))).collect()
+    }
+
+    fn fingerprint(&self) -> u64 {
+        BuildFxHasher.hash_one(&self.bytes)
+    }
+
+    #[inline]
+    fn same(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+impl<T: Clone + PartialEq + Hash> Space for SparseGrid<T> {
+    type Cell = Option<T>;
+
+    #[inline]
+    fn read(&self, p: Point) -> Option<Option<T>> {
+        Some(self.get(p).cloned())
+    }
+
+    #[inline]
+    fn write(&mut self, p: Point, cell: Option<T>) {
+        match cell {
+            Some(value) => self.insert(p, value),
+            None => self.remove(p),
+        };
+    }
+
+    fn candidates(&self, offsets: &[Point]) -> Vec<Point> {
+        let mut candidates = FastSet::with_capacity(self.len() * (offsets.len() + 1));
+        for p in self.points() {
+            candidates.insert(p);
+            candidates.extend(offsets.iter().map(|&o| p + o));
+        }
+        candidates.into_iter().collect()
+    }
+
+    fn fingerprint(&self) -> u64 {
+        // Order independent, as iteration order of the map is arbitrary.
+        self.iter().fold(0, |hash, cell| hash.wrapping_add(BuildFxHasher.hash_one(cell)))
+    }
+
+    /// Compares only the cells, as the bounding box keeps every cell ever inserted.
+    #[inline]
+    fn same(&self, other: &Self) -> bool {
+        self.cells == other.cells
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Generation {
+    /// Cells whose state changed in this generation.
+    pub changed: usize,
+    /// Cells considered live after this generation.
+    pub live: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// Generation `generation` (starting from 1) changed nothing.
+    Fixpoint { generation: usize },
+    /// The state after generation `start + length` is the same as after generation `start`,
+    /// where generation 0 is the initial state.
+    Cycle { start: usize, length: usize },
+    /// Stopped after the maximum number of generations.
+    Limit,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct History {
+    pub generations: Vec<Generation>,
+    pub outcome: Outcome,
+}
+
+impl History {
+    /// Total number of cell changes over all generations.
+    pub fn changed(&self) -> usize {
+        self.generations.iter().map(|g| g.changed).sum()
+    }
+}
+
+/// Updates every cell at once until a fixpoint or cycle is reached, or `limit` generations
+/// have run. A copy of each generation is kept to confirm cycles, as fingerprints can collide.
+pub fn run<S: Space>(
+    space: &mut S,
+    offsets: &[Point],
+    live: impl Fn(&S::Cell) -> bool,
+    rule: impl Fn(&S::Cell, usize) -> S::Cell,
+    limit: usize,
+) -> History {
+    let mut generations = Vec::new();
+    let mut seen: FastMap<u64, Vec<(usize, S)>> = FastMap::new();
+    seen.insert(space.fingerprint(), vec![(0, space.clone())]);
+
+    for generation in 1..=limit {
+        let candidates = space.candidates(offsets);
+        let changed = wave(space, &candidates, offsets, &live, &rule);
+        let live_cells = count(space, offsets, &live);
+        generations.push(Generation { changed: changed.len(), live: live_cells });
+
+        if changed.is_empty() {
+            return History { generations, outcome: Outcome::Fixpoint { generation } };
+        }
+
+        let states = seen.entry(space.fingerprint()).or_default();
+        if let Some(&(start, _)) = states.iter().find(|(_, state)| state.same(space)) {
+            let outcome = Outcome::Cycle { start, length: generation - start };
+            return History { generations, outcome };
+        }
+        states.push((generation, space.clone()));
+    }
+
+    History { generations, outcome: Outcome::Limit }
+}
+
+/// Repeatedly updates only the cells next to a cell that changed in the previous wave, starting
+/// with every cell, until nothing changes or `limit` waves have run. All cells in a wave see the
+/// state from before the wave.
+pub fn settle<S: Space>(
+    space: &mut S,
+    offsets: &[Point],
+    live: impl Fn(&S::Cell) -> bool,
+    rule: impl Fn(&S::Cell, usize) -> S::Cell,
+    limit: usize,
+) -> History {
+    let mut generations = Vec::new();
+    let mut todo = space.candidates(offsets);
+    let mut live_cells = count(space, offsets, &live);
+
+    for generation in 1..=limit {
+        let changed = wave(space, &todo, offsets, &live, &rule);
+        if changed.is_empty() {
+            generations.push(Generation { changed: 0, live: live_cells });
+            return History { generations, outcome: Outcome::Fixpoint { generation } };
+        }
+
+        let mut next = FastSet::with_capacity(changed.len() * (offsets.len() + 1));
+        for &(p, was_live) in &changed {
+            let is_live = space.read(p).is_some_and(|cell| live(&cell));
+            live_cells = live_cells + usize::from(is_live) - usize::from(was_live);
+            next.insert(p);
+            next.extend(offsets.iter().map(|&o| p + o).filter(|&n| space.read(n).is_some()));
+        }
+
+        generations.push(Generation { changed: changed.len(), live: live_cells });
+        todo = next.into_iter().collect();
+    }
+
+    History { generations, outcome: Outcome::Limit }
+}
+
+/// Applies the rule to `points` based on the current state, then writes all changes. Returns the
+/// changed points and whether each was live before the change.
+fn wave<S: Space>(
+    space: &mut S,
+    points: &[Point],
+    offsets: &[Point],
+    live: &impl Fn(&S::Cell) -> bool,
+    rule: &impl Fn(&S::Cell, usize) -> S::Cell,
+) -> Vec<(Point, bool)> {
+    let updates: Vec<_> = points
+        .iter()
+        .filter_map(|&p| {
+            let cell = space.read(p)?;
+            let neighbors = offsets
+                .iter()
+                .filter(|&&o| space.read(p + o).is_some_and(|n| live(&n)))
+                .count();
+            let next = rule(&cell, neighbors);
+            (next != cell).then(|| (p, live(&cell), next))
+        })
+        .collect();
+
+    updates
+        .into_iter()
+        .map(|(p, was_live, next)| {
+            space.write(p, next);
+            (p, was_live)
+        })
+        .collect()
+}
+
+fn count<S: Space>(space: &S, offsets: &[Point], live: &impl Fn(&S::Cell) -> bool) -> usize {
+    let candidates = space.candidates(offsets);
+    candidates.into_iter().filter(|&p| space.read(p).is_some_and(|cell| live(&cell))).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Conway's Game of Life.
+    fn life(cell: &u8, neighbors: usize) -> u8 {
+        match (cell, neighbors) {
+            (b'#', 2 | 3) | (b'.', 3) => b'#',
+            _ => b'.',
+        }
+    }
+
+    #[test]
+    fn grid() {
+        let alive = |&c: &u8| c == b'#';
+
+        let mut blinker = Grid::parse(".....\n..#..\n..#..\n..#..\n.....");
+        let history = run(&mut blinker, &DIAGONAL, alive, life, 10);
+        assert_eq!(history.outcome, Outcome::Cycle { start: 0, length: 2 });
+        assert_eq!(history.generations[0], Generation { changed: 4, live: 3 });
+
+        let mut block = Grid::parse("....\n.##.\n.#..\n....");
+        let history = run(&mut block, &DIAGONAL, alive, life, 10);
+        assert_eq!(history.outcome, Outcome::Fixpoint { generation: 2 });
+        assert_eq!(block, Grid::parse("....\n.##.\n.##.\n...."));
+    }
+
+    #[test]
+    fn sparse() {
+        let grid = Grid::parse("####.\n####.\n####.\n....#");
+        let mut sparse = SparseGrid::from_grid(&grid, |&b| b == b'#');
+        let mut expected = sparse.clone();
+        // Remove cells with fewer than 3 neighbors.
+        let erode = |cell: &Option<u8>, n: usize| cell.filter(|_| n >= 3);
+
+        let history = settle(&mut sparse, &DIAGONAL, Option::is_some, erode, 10);
+        let changed: Vec<_> = history.generations.iter().map(|g| g.changed).collect();
+        assert_eq!(changed, [1, 0]);
+        assert_eq!(history.outcome, Outcome::Fixpoint { generation: 2 });
+        assert_eq!(history.generations.last().unwrap().live, 12);
+
+        assert_eq!(run(&mut expected, &DIAGONAL, Option::is_some, erode, 10), history);
+        assert_eq!(sparse, expected);
+    }
+}
@@ -0,0 +1,297 @@
+//! Grid of booleans packed into one bit per cell.
+//!
+//! Uses an eighth of the memory of a `Grid<bool>` and allows whole rows to be combined 64 cells
+//! at a time. Each row starts at a new `u64` word, bit `x % 64` of word `x / 64` holds column `x`.
+//! Bits past the end of a row are always zero, so [`BitGrid::count_ones`] and the bitwise
+//! operators never see stray cells.
+use crate::util::grid::Grid;
+use crate::util::point::*;
+use std::ops::{BitAndAssign, BitOrAssign, BitXorAssign, Index};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    pub width: i32,
+    pub height: i32,
+    stride: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    pub fn new(width: i32, height: i32) -> Self {
+        let stride = (width as usize).div_ceil(64);
+        BitGrid { width, height, stride, words: vec![0; stride * height as usize] }
+    }
+
+    /// Sets the cells of `grid` for which `predicate` holds.
+    pub fn from_grid<T>(grid: &Grid<T>, predicate: impl Fn(&T) -> bool) -> Self {
+        let mut bits = BitGrid::new(grid.width, grid.height);
+        for y in 0..grid.height {
+            for x in 0..grid.width {
+                let point = Point::new(x, y);
+                if predicate(&grid[point]) {
+                    bits.set(point, true);
+                }
+            }
+        }
+        bits
+    }
+
+    /// Converts back into bytes, with `on` for set cells and `off` for the rest.
+    pub fn to_grid(&self, on: u8, off: u8) -> Grid<u8> {
+        let mut grid = Grid::new(self.width, self.height, off);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let point = Point::new(x, y);
+                if self.get(point) {
+                    grid[point] = on;
+                }
+            }
+        }
+        grid
+    }
+
+    #[inline]
+    pub fn contains(&self, point: Point) -> bool {
+        point.x >= 0 && point.x < self.width && point.y >= 0 && point.y < self.height
+    }
+
+    /// Whether the cell is set. Points outside the grid are never set.
+    #[inline]
+    pub fn get(&self, p: Point) -> bool {
+        self.contains(p) && {
+            let (index, bit) = self.position(p);
+            self.words[index] & bit != 0
+        }
+    }
+
+    /// Panics if `p` is outside the grid, as that would write into the padding or the next row.
+    #[inline]
+    pub fn set(&mut self, p: Point, value: bool) {
+        let (index, bit) = self.position(p);
+        if value {
+            self.words[index] |= bit;
+        } else {
+            self.words[index] &= !bit;
+        }
+    }
+
+    /// Flips the cell and returns its new value. Panics if `p` is outside the grid.
+    #[inline]
+    pub fn toggle(&mut self, p: Point) -> bool {
+        let (index, bit) = self.position(p);
+        self.words[index] ^= bit;
+        self.words[index] & bit != 0
+    }
+
+    #[inline]
+    fn position(&self, p: Point) -> (usize, u64) {
+        assert!(self.contains(p), "{p:?} out of bounds");
+        let index = p.y as usize * self.stride + p.x as usize / 64;
+        (index, 1 << (p.x % 64))
+    }
+
+    #[inline]
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    #[inline]
+    pub fn row(&self, y: i32) -> &[u64] {
+        let start = y as usize * self.stride;
+        &self.words[start..start + self.stride]
+    }
+
+    #[inline]
+    pub fn row_mut(&mut self, y: i32) -> &mut [u64] {
+        let start = y as usize * self.stride;
+        &mut self.words[start..start + self.stride]
+    }
+
+    /// Neighbors of `p` at each of the `offsets` that are inside the grid, with their value.
+    #[inline]
+    pub fn neighbors<'a>(
+        &'a self,
+        p: Point,
+        offsets: &'a [Point],
+    ) -> impl Iterator<Item = (Point, bool)> + 'a {
+        offsets.iter().map(move |&o| p + o).filter(|&n| self.contains(n)).map(|n| (n, self.get(n)))
+    }
+
+    #[inline]
+    pub fn neighbors4(&self, p: Point) -> impl Iterator<Item = (Point, bool)> {
+        self.neighbors(p, &ORTHOGONAL)
+    }
+
+    #[inline]
+    pub fn neighbors8(&self, p: Point) -> impl Iterator<Item = (Point, bool)> {
+        self.neighbors(p, &DIAGONAL)
+    }
+
+    /// Number of set cells among the orthogonal and diagonal neighbors.
+    #[inline]
+    pub fn count_neighbors8(&self, p: Point) -> usize {
+        DIAGONAL.iter().filter(|&&o| self.get(p + o)).count()
+    }
+
+    /// Sets every clear cell orthogonally connected to `start` through clear cells, returning
+    /// the number of cells set.
+    pub fn flood_fill(&mut self, start: Point) -> usize {
+        if !self.contains(start) || self.get(start) {
+            return 0;
+        }
+
+        let mut todo = vec![start];
+        self.set(start, true);
+        let mut filled = 1;
+
+        while let Some(p) = todo.pop() {
+            for o in ORTHOGONAL {
+                let next = p + o;
+                if self.contains(next) && !self.get(next) {
+                    self.set(next, true);
+                    todo.push(next);
+                    filled += 1;
+                }
+            }
+        }
+
+        filled
+    }
+
+    /// Flips every cell.
+    pub fn invert(&mut self) {
+        for word in &mut self.words {
+            *word = !*word;
+        }
+        self.clear_padding();
+    }
+
+    /// Moves every cell `dx` columns to the right, or to the left when negative. Cells moved past
+    /// an edge are dropped and the vacated cells are cleared.
+    pub fn shift_x(&mut self, dx: i32) {
+        let (words, bits) = (dx.unsigned_abs() as usize / 64, dx.unsigned_abs() % 64);
+        if self.stride == 0 {
+            return;
+        }
+
+        for row in self.words.chunks_exact_mut(self.stride) {
+            let old = row.to_vec();
+            // Words outside the row read as zero, so shifted in cells are clear.
+            let word = |i: Option<usize>| i.and_then(|i| old.get(i)).copied().unwrap_or(0);
+
+            for (i, new) in row.iter_mut().enumerate() {
+                *new = if dx >= 0 {
+                    let from = i.checked_sub(words);
+                    let carry = from.and_then(|f| f.checked_sub(1));
+                    let carry = if bits == 0 { 0 } else { word(carry) >> (64 - bits) };
+                    (word(from) << bits) | carry
+                } else {
+                    let from = i + words;
+                    let carry = if bits == 0 { 0 } else { word(Some(from + 1)) << (64 - bits) };
+                    (word(Some(from)) >> bits) | carry
+                };
+            }
+        }
+
+        self.clear_padding();
+    }
+
+    /// Moves every row `dy` rows down, or up when negative. Rows moved past an edge are dropped
+    /// and the vacated rows are cleared.
+    pub fn shift_y(&mut self, dy: i32) {
+        let rows = (dy.unsigned_abs() as usize).min(self.height as usize);
+        let offset = rows * self.stride;
+        let len = self.words.len();
+
+        if dy >= 0 {
+            self.words.copy_within(..len - offset, offset);
+            self.words[..offset].fill(0);
+        } else {
+            self.words.copy_within(offset.., 0);
+            self.words[len - offset..].fill(0);
+        }
+    }
+
+    /// Clears the unused bits after the last column of each row.
+    fn clear_padding(&mut self) {
+        let used = self.width as usize % 64;
+        if used != 0 {
+            let mask = (1 << used) - 1;
+            for row in self.words.chunks_exact_mut(self.stride) {
+                row[self.stride - 1] &= mask;
+            }
+        }
+    }
+}
+
+macro_rules! bitwise {
+    ($trait:ident, $method:ident, $op:tt) => {
+        impl $trait<&BitGrid> for BitGrid {
+            #[inline]
+            fn $method(&mut self, rhs: &BitGrid) {
+                assert_eq!((self.width, self.height), (rhs.width, rhs.height), "Grid sizes differ");
+                for (a, b) in self.words.iter_mut().zip(&rhs.words) {
+                    *a $op *b;
+                }
+            }
+        }
+    };
+}
+
+bitwise!(BitAndAssign, bitand_assign, &=);
+bitwise!(BitOrAssign, bitor_assign, |=);
+bitwise!(BitXorAssign, bitxor_assign, ^=);
+
+impl Index<Point> for BitGrid {
+    type Output = bool;
+
+    #[inline]
+    fn index(&self, index: Point) -> &Self::Output {
+        if self.get(index) { &true } else { &false }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bits() {
+        let grid = Grid::parse("#..#\n.##.\n....");
+        let mut bits = BitGrid::from_grid(&grid, |&b| b == b'#');
+        assert_eq!(bits.count_ones(), 4);
+        assert!(bits[Point::new(3, 0)] && !bits[Point::new(3, 1)]);
+        assert_eq!(bits.count_neighbors8(Point::new(2, 0)), 3);
+        assert_eq!(bits.neighbors4(ORIGIN).filter(|&(_, set)| set).count(), 0);
+        assert_eq!(bits.to_grid(b'#', b'.'), grid);
+
+        let mut shifted = bits.clone();
+        shifted.shift_x(1);
+        shifted.shift_y(1);
+        assert_eq!(shifted.to_grid(b'#', b'.'), Grid::parse("....\n.#..\n..##"));
+        shifted.shift_x(-2);
+        shifted.shift_y(-1);
+        assert_eq!(shifted.to_grid(b'#', b'.'), Grid::parse("....\n##..\n...."));
+
+        bits ^= &shifted;
+        assert_eq!(bits.to_grid(b'#', b'.'), Grid::parse("#..#\n#.#.\n...."));
+        bits.invert();
+        assert_eq!(bits.count_ones(), 8);
+
+        let mut walls = BitGrid::from_grid(&Grid::parse("...\n###\n..."), |&b| b == b'#');
+        assert_eq!(walls.flood_fill(ORIGIN), 3);
+        assert_eq!(walls.count_ones(), 6);
+    }
+
+    #[test]
+    fn wide_shift() {
+        let mut bits = BitGrid::new(150, 1);
+        bits.set(Point::new(60, 0), true);
+        bits.shift_x(70);
+        assert!(bits.get(Point::new(130, 0)));
+        bits.shift_x(-129);
+        assert!(bits.get(Point::new(1, 0)));
+        bits.shift_x(149);
+        assert_eq!(bits.count_ones(), 0);
+    }
+}
@@ -0,0 +1,212 @@
+//! [Coordinate compression] for sparse geometry with huge coordinates.
+//!
+//! Each distinct coordinate gets a cell of width 1, and each gap between two consecutive
+//! coordinates gets a single cell covering the whole gap. The compressed cells still tile the
+//! original axis, so areas can be recovered exactly from [`CompressedAxis::width`], while shapes
+//! keep their topology. For example the x coordinates `2, 3, 10` become:
+//!
+//! ```none
+//!   cell    0   1   2       3
+//!   start   2   3   4       10
+//!   width   1   1   6       1
+//! ```
+//!
+//! [`CompressedGrid`] combines two axes and surrounds them with a border of empty cells, so the
+//! outside of a polygon is one connected region that can be flood filled from [`ORIGIN`].
+//!
+//! [Coordinate compression]: https://en.wikipedia.org/wiki/Coordinate_compression
+use crate::util::grid::Grid;
+use crate::util::integer::*;
+use crate::util::point::*;
+
+pub struct CompressedAxis<T> {
+    /// Distinct input coordinates in increasing order.
+    values: Vec<T>,
+    /// Cell index of each of `values`.
+    indices: Vec<i32>,
+    /// First original coordinate covered by each cell.
+    starts: Vec<T>,
+    /// Number of original coordinates covered by each cell.
+    widths: Vec<T>,
+}
+
+impl<T: Integer<T> + Ord> CompressedAxis<T> {
+    pub fn new(values: impl IntoIterator<Item = T>) -> Self {
+        CompressedAxis::build(values, false)
+    }
+
+    /// Adds a cell of width zero before the first and after the last coordinate.
+    pub fn with_border(values: impl IntoIterator<Item = T>) -> Self {
+        CompressedAxis::build(values, true)
+    }
+
+    fn build(values: impl IntoIterator<Item = T>, border: bool) -> Self {
+        let mut values: Vec<_> = values.into_iter().collect();
+        values.sort_unstable();
+        values.dedup();
+
+        let mut indices = Vec::with_capacity(values.len());
+        let mut starts = Vec::with_capacity(2 * values.len() + 2);
+        let mut widths = Vec::with_capacity(2 * values.len() + 2);
+
+        if border && let Some(&first) = values.first() {
+            starts.push(first);
+            widths.push(T::ZERO);
+        }
+        for (i, &value) in values.iter().enumerate() {
+            indices.push(starts.len() as i32);
+            starts.push(value);
+            widths.push(T::ONE);
+
+            if let Some(&next) = values.get(i + 1)
+                && next - value > T::ONE
+            {
+                starts.push(value + T::ONE);
+                widths.push(next - value - T::ONE);
+            }
+        }
+        if border && let Some(&last) = values.last() {
+            starts.push(last);
+            widths.push(T::ZERO);
+        }
+
+        CompressedAxis { values, indices, starts, widths }
+    }
+
+    /// Number of compressed cells.
+    #[inline]
+    pub fn len(&self) -> i32 {
+        self.starts.len() as i32
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.starts.is_empty()
+    }
+
+    /// Cell of one of the original coordinates, or `None` if it wasn't part of the input.
+    #[inline]
+    pub fn try_index(&self, value: T) -> Option<i32> {
+        self.values.binary_search(&value).ok().map(|i| self.indices[i])
+    }
+
+    #[inline]
+    pub fn index(&self, value: T) -> i32 {
+        self.try_index(value).expect("Coordinate to be one of the compressed values")
+    }
+
+    /// Cell containing any coordinate between the first and last input value, including
+    /// coordinates that fall in a gap.
+    pub fn locate(&self, value: T) -> Option<i32> {
+        let (first, last) = (*self.values.first()?, *self.values.last()?);
+        if value < first || value > last {
+            return None;
+        }
+        let after = self.starts.partition_point(|&start| start <= value);
+        // Skip back over the zero width border cell, which shares its start with the first cell.
+        (0..after).rev().find(|&i| self.widths[i] != T::ZERO).map(|i| i as i32)
+    }
+
+    /// First original coordinate covered by a cell.
+    #[inline]
+    pub fn start(&self, index: i32) -> T {
+        self.starts[index as usize]
+    }
+
+    /// Number of original coordinates covered by a cell.
+    #[inline]
+    pub fn width(&self, index: i32) -> T {
+        self.widths[index as usize]
+    }
+
+    /// Number of original coordinates covered by cells `from` to `to` inclusive.
+    pub fn span(&self, from: i32, to: i32) -> T {
+        (from..=to).fold(T::ZERO, |total, index| total + self.width(index))
+    }
+}
+
+/// Compressed x and y axes, each with a border of empty cells.
+pub struct CompressedGrid<T> {
+    pub xs: CompressedAxis<T>,
+    pub ys: CompressedAxis<T>,
+}
+
+impl<T: Integer<T> + Ord> CompressedGrid<T> {
+    pub fn from_points(points: impl IntoIterator<Item = (T, T)>) -> Self {
+        let (xs, ys): (Vec<_>, Vec<_>) = points.into_iter().unzip();
+        CompressedGrid { xs: CompressedAxis::with_border(xs), ys: CompressedAxis::with_border(ys) }
+    }
+
+    /// Compresses the corners of rectangles given by their top left and bottom right corners,
+    /// both inclusive.
+    pub fn from_rects(rects: impl IntoIterator<Item = ((T, T), (T, T))>) -> Self {
+        CompressedGrid::from_points(rects.into_iter().flat_map(|(min, max)| [min, max]))
+    }
+
+    /// Compressed location of one of the input points.
+    #[inline]
+    pub fn point(&self, (x, y): (T, T)) -> Point {
+        Point::new(self.xs.index(x), self.ys.index(y))
+    }
+
+    /// Empty grid with one cell per compressed cell.
+    #[inline]
+    pub fn grid<U: Copy>(&self, fill: U) -> Grid<U> {
+        Grid::new(self.xs.len(), self.ys.len(), fill)
+    }
+
+    /// Original area covered by a compressed cell.
+    #[inline]
+    pub fn area(&self, p: Point) -> T {
+        self.xs.width(p.x) * self.ys.width(p.y)
+    }
+
+    /// Sets every compressed cell covered by the rectangle from `min` to `max` inclusive, which
+    /// must be corners of one of the input rectangles.
+    pub fn fill_rect<U: Copy>(&self, grid: &mut Grid<U>, min: (T, T), max: (T, T), value: U) {
+        let (min, max) = (self.point(min), self.point(max));
+        for y in min.y..=max.y {
+            grid.row_mut(y)[min.x as usize..=max.x as usize].fill(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn axis() {
+        let axis = CompressedAxis::new([10_u64, 2, 3, 2]);
+        assert_eq!(axis.len(), 4);
+        assert_eq!((axis.index(2), axis.index(3), axis.index(10)), (0, 1, 3));
+        assert_eq!((axis.start(2), axis.width(2)), (4, 6));
+        assert_eq!(axis.try_index(5), None);
+        assert_eq!(axis.locate(5), Some(2));
+        assert_eq!(axis.span(0, 3), 9);
+
+        let border = CompressedAxis::with_border([0_u64, 100]);
+        assert_eq!(border.len(), 5);
+        assert_eq!(border.index(0), 1);
+        assert_eq!(border.locate(0), Some(1));
+        assert_eq!(border.span(0, 4), 101);
+    }
+
+    #[test]
+    fn grid() {
+        let rects = [((0_i64, 0), (9, 1)), ((1_000, 5), (1_000, 100))];
+        let compressed = CompressedGrid::from_rects(rects);
+        let mut grid = compressed.grid(false);
+        for (min, max) in rects {
+            compressed.fill_rect(&mut grid, min, max, true);
+        }
+
+        assert_eq!((grid.width, grid.height), (7, 8));
+        let covered = (0..grid.height)
+            .flat_map(|y| (0..grid.width).map(move |x| Point::new(x, y)))
+            .filter(|&p| grid[p])
+            .map(|p| compressed.area(p))
+            .sum::<i64>();
+        assert_eq!(covered, 10 * 2 + 96);
+    }
+}
@@ -0,0 +1,205 @@
+//! Compact headings for grid movement.
+//!
+//! [`Direction`] holds the 4 orthogonal headings and [`Direction8`] adds the diagonals. Both
+//! list their variants clockwise starting from [`Direction::Up`], so [`Direction::index`] can
+//! be used to store per heading state in an array instead of a map keyed by [`Point`].
+//!
+//! `turn_left` and `turn_right` move one variant counter clockwise or clockwise, which is a
+//! quarter turn for [`Direction`] and an eighth of a turn for [`Direction8`].
+//!
+//! Parsing accepts arrows `^>v<`, letters `UDLR` and compass points `NESW`, in either case for
+//! letters. [`Direction8`] parses `NE`, `SE`, `SW` and `NW` from strings too.
+use crate::util::integer::*;
+use crate::util::point::*;
+use std::fmt;
+use std::str::FromStr;
+
+/// Error returned when parsing a direction from an unknown byte or string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownDirection(pub String);
+
+impl fmt::Display for UnknownDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown direction {:?}", self.0)
+    }
+}
+
+macro_rules! direction {
+    ($name:ident, $count:literal, $($variant:ident = ($x:literal, $y:literal)),+) => {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub enum $name {
+            $($variant),+
+        }
+
+        impl $name {
+            /// Every direction, clockwise starting from up.
+            pub const ALL: [$name; $count] = [$($name::$variant),+];
+
+            /// Position in [`Self::ALL`].
+            #[inline]
+            pub const fn index(self) -> usize {
+                self as usize
+            }
+
+            #[inline]
+            pub fn iter() -> impl Iterator<Item = $name> {
+                $name::ALL.into_iter()
+            }
+
+            #[inline]
+            #[must_use]
+            pub const fn turn_left(self) -> Self {
+                $name::ALL[(self.index() + $count - 1) % $count]
+            }
+
+            #[inline]
+            #[must_use]
+            pub const fn turn_right(self) -> Self {
+                $name::ALL[(self.index() + 1) % $count]
+            }
+
+            #[inline]
+            #[must_use]
+            pub const fn reverse(self) -> Self {
+                $name::ALL[(self.index() + $count / 2) % $count]
+            }
+
+            /// Unit step in this direction, with y increasing downwards.
+            #[inline]
+            pub const fn as_point(self) -> Point {
+                match self {
+                    $($name::$variant => Point::new($x, $y)),+
+                }
+            }
+        }
+
+        impl<T: Signed<T>> From<$name> for Point<T> {
+            #[inline]
+            fn from(direction: $name) -> Self {
+                let Point { x, y } = direction.as_point();
+                Point::new(unit(x), unit(y))
+            }
+        }
+
+        impl TryFrom<u8> for $name {
+            type Error = UnknownDirection;
+
+            #[inline]
+            fn try_from(byte: u8) -> Result<Self, Self::Error> {
+                Direction::from_byte(byte)
+                    .map(|direction| $name::ALL[direction.index() * $count / 4])
+                    .ok_or_else(|| UnknownDirection((byte as char).to_string()))
+            }
+        }
+    };
+}
+
+direction!(Direction, 4, Up = (0, -1), Right = (1, 0), Down = (0, 1), Left = (-1, 0));
+
+direction!(
+    Direction8,
+    8,
+    Up = (0, -1),
+    UpRight = (1, -1),
+    Right = (1, 0),
+    DownRight = (1, 1),
+    Down = (0, 1),
+    DownLeft = (-1, 1),
+    Left = (-1, 0),
+    UpLeft = (-1, -1)
+);
+
+impl Direction {
+    #[inline]
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            b'^' | b'U' | b'u' | b'N' | b'n' => Some(Direction::Up),
+            b'>' | b'R' | b'r' | b'E' | b'e' => Some(Direction::Right),
+            b'v' | b'D' | b'd' | b'S' | b's' => Some(Direction::Down),
+            b'<' | b'L' | b'l' | b'W' | b'w' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = UnknownDirection;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.as_bytes() {
+            &[byte] => Direction::try_from(byte),
+            _ => Err(UnknownDirection(s.to_string())),
+        }
+    }
+}
+
+impl FromStr for Direction8 {
+    type Err = UnknownDirection;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "NE" => Ok(Direction8::UpRight),
+            "SE" => Ok(Direction8::DownRight),
+            "SW" => Ok(Direction8::DownLeft),
+            "NW" => Ok(Direction8::UpLeft),
+            _ => match s.as_bytes() {
+                &[byte] => Direction8::try_from(byte),
+                _ => Err(UnknownDirection(s.to_string())),
+            },
+        }
+    }
+}
+
+impl From<Direction> for Direction8 {
+    #[inline]
+    fn from(direction: Direction) -> Self {
+        Direction8::ALL[direction.index() * 2]
+    }
+}
+
+/// Converts a unit coordinate of -1, 0 or 1.
+#[inline]
+fn unit<T: Signed<T>>(n: i32) -> T {
+    match n {
+        -1 => T::MINUS_ONE,
+        0 => T::ZERO,
+        _ => T::ONE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn direction() {
+        let up = Direction::try_from(b'^').unwrap();
+        assert_eq!(up, Direction::Up);
+        assert_eq!(up.turn_right(), Direction::Right);
+        assert_eq!(up.turn_left(), Direction::Left);
+        assert_eq!(up.reverse(), Direction::Down);
+        let steps: Vec<_> = Direction::iter().map(Direction::as_point).collect();
+        assert_eq!(steps, [UP, RIGHT, DOWN, LEFT]);
+        assert!(Direction::iter().all(|d| d.as_point().clockwise() == d.turn_right().as_point()));
+
+        let parsed: Result<Vec<_>, _> = "NESWudlr<>".bytes().map(Direction::try_from).collect();
+        let indices: Vec<_> = parsed.unwrap().into_iter().map(Direction::index).collect();
+        assert_eq!(indices, [0, 1, 2, 3, 0, 2, 3, 1, 3, 1]);
+        assert_eq!("x".parse::<Direction>(), Err(UnknownDirection("x".to_string())));
+        assert_eq!(Point::<i64>::from(Direction::Left), Point::new(-1, 0));
+    }
+
+    #[test]
+    fn direction8() {
+        let ne: Direction8 = "ne".parse().unwrap();
+        assert_eq!(ne, Direction8::UpRight);
+        assert_eq!(ne.turn_right(), Direction8::Right);
+        assert_eq!(ne.reverse(), Direction8::DownLeft);
+        assert_eq!("W".parse(), Ok(Direction8::Left));
+        assert_eq!(Direction8::from(Direction::Down), Direction8::Down);
+
+        let mut offsets: Vec<_> = Direction8::iter().map(Direction8::as_point).collect();
+        offsets.sort_unstable_by_key(|p| (p.y, p.x));
+        assert_eq!(offsets, DIAGONAL);
+    }
+}
@@ -0,0 +1,241 @@
+//! Exact geometry on polygons with integer vertices, working directly on the list of vertices.
+//!
+//! A polygon is given by its vertices in order, clockwise or counter clockwise, with an edge from
+//! the last vertex back to the first. It must be simple, that is edges only meet at shared
+//! vertices. The polygon is the closed region enclosed by the edges, so points on an edge are
+//! inside.
+//!
+//! * [`shoelace`] computes twice the signed area with the [shoelace formula], which stays an
+//!   integer even when the area itself is a half.
+//! * [`boundary_points`], [`interior_points`] and [`lattice_points`] count integer points using
+//!   [Pick's theorem], for example the cells dug out by a closed loop of trenches.
+//! * [`ray_cast`] and [`winding`] locate a point relative to the polygon.
+//! * [`rect_inside`] checks that a whole rectangle lies within a rectilinear polygon, one whose
+//!   edges are all horizontal or vertical, without rasterizing it.
+//!
+//! [shoelace formula]: https://en.wikipedia.org/wiki/Shoelace_formula
+//! [Pick's theorem]: https://en.wikipedia.org/wiki/Pick%27s_theorem
+use crate::util::integer::*;
+use crate::util::point::*;
+use crate::util::rect::Rect;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Location {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+/// Twice the signed area, positive when the vertices go counter clockwise with y pointing up,
+/// which is clockwise on screen where y points down.
+pub fn shoelace<T: Signed<T>>(polygon: &[Point<T>]) -> T {
+    edges(polygon).fold(T::ZERO, |total, (a, b)| total + a.x * b.y - b.x * a.y)
+}
+
+/// Area enclosed by the edges, rounded down if it's not a whole number.
+pub fn area<T: Signed<T>>(polygon: &[Point<T>]) -> T {
+    shoelace(polygon).abs() / T::TWO
+}
+
+/// Number of integer points on the edges.
+pub fn boundary_points<T: Signed<T>>(polygon: &[Point<T>]) -> T {
+    edges(polygon).fold(T::ZERO, |total, (a, b)| total + gcd((b.x - a.x).abs(), (b.y - a.y).abs()))
+}
+
+/// Number of integer points strictly inside, from Pick's theorem `A = i + b / 2 - 1`.
+pub fn interior_points<T: Signed<T>>(polygon: &[Point<T>]) -> T {
+    (shoelace(polygon).abs() - boundary_points(polygon) + T::TWO) / T::TWO
+}
+
+/// Number of integer points inside or on the edges.
+pub fn lattice_points<T: Signed<T>>(polygon: &[Point<T>]) -> T {
+    interior_points(polygon) + boundary_points(polygon)
+}
+
+/// Locates `p` by counting how many edges a ray from `p` towards positive x crosses.
+pub fn ray_cast<T: Signed<T>>(polygon: &[Point<T>], p: Point<T>) -> Location {
+    if on_boundary(polygon, p) {
+        return Location::Boundary;
+    }
+
+    let mut inside = false;
+    for (a, b) in edges(polygon) {
+        // Half open in y, so a ray through a vertex counts exactly one of its two edges.
+        if (a.y > p.y) != (b.y > p.y) {
+            let side = cross(a, b, p);
+            if (b.y > a.y && side > T::ZERO) || (b.y < a.y && side < T::ZERO) {
+                inside = !inside;
+            }
+        }
+    }
+
+    if inside { Location::Inside } else { Location::Outside }
+}
+
+/// Locates `p` from the [winding number], which works for any orientation of the polygon.
+///
+/// [winding number]: https://en.wikipedia.org/wiki/Winding_number
+pub fn winding<T: Signed<T>>(polygon: &[Point<T>], p: Point<T>) -> Location {
+    if on_boundary(polygon, p) {
+        Location::Boundary
+    } else if winding_number(polygon, p) != 0 {
+        Location::Inside
+    } else {
+        Location::Outside
+    }
+}
+
+/// Number of times the edges wind around `p`, with counter clockwise turns counting as positive
+/// when y points up. Zero for points outside. Points on an edge have no meaningful result.
+pub fn winding_number<T: Signed<T>>(polygon: &[Point<T>], p: Point<T>) -> i32 {
+    let mut winding = 0;
+    for (a, b) in edges(polygon) {
+        if a.y <= p.y {
+            if b.y > p.y && cross(a, b, p) > T::ZERO {
+                winding += 1;
+            }
+        } else if b.y <= p.y && cross(a, b, p) < T::ZERO {
+            winding -= 1;
+        }
+    }
+    winding
+}
+
+/// Whether every point of `rect`, taken as the region from `min` to `max`, is inside or on the
+/// edges of a rectilinear polygon.
+///
+/// The rectangle is inside when no edge passes through its interior and its center is inside.
+/// A rectangle of zero width or height is a line, which is checked at its ends and between each
+/// pair of neighboring vertex coordinates along it. Coordinates are doubled internally so that
+/// centers are integers, so they must fit in `T` when doubled.
+pub fn rect_inside<T: Signed<T>>(polygon: &[Point<T>], rect: &Rect<T>) -> bool {
+    if rect.is_empty() {
+        return true;
+    }
+    let doubled: Vec<_> = polygon.iter().map(|&p| p * T::TWO).collect();
+    let (min, max) = (rect.min * T::TWO, rect.max * T::TWO);
+
+    if min.x != max.x && min.y != max.y {
+        let center = Point::new((min.x + max.x) / T::TWO, (min.y + max.y) / T::TWO);
+        let crossed = edges(&doubled).any(|(a, b)| {
+            let lo = Point::new(a.x.min(b.x), a.y.min(b.y));
+            let hi = Point::new(a.x.max(b.x), a.y.max(b.y));
+            lo.x < max.x && hi.x > min.x && lo.y < max.y && hi.y > min.y
+        });
+        return !crossed && ray_cast(&doubled, center) != Location::Outside;
+    }
+
+    // A line, or a single point. Between consecutive vertex coordinates along the line no edge
+    // can start or end, so one sample per gap is enough.
+    let horizontal = min.y == max.y;
+    let along = |p: Point<T>| if horizontal { p.x } else { p.y };
+    let at = |c: T| if horizontal { Point::new(c, min.y) } else { Point::new(min.x, c) };
+
+    let mut stops: Vec<_> = doubled
+        .iter()
+        .map(|&v| along(v))
+        .filter(|&c| along(min) < c && c < along(max))
+        .collect();
+    stops.push(along(min));
+    stops.push(along(max));
+    stops.sort_unstable();
+    stops.dedup();
+
+    let inside = |c: T| ray_cast(&doubled, at(c)) != Location::Outside;
+    inside(along(min))
+        && inside(along(max))
+        && stops.windows(2).all(|pair| inside((pair[0] + pair[1]) / T::TWO))
+}
+
+/// Whether `p` lies on any edge.
+fn on_boundary<T: Signed<T>>(polygon: &[Point<T>], p: Point<T>) -> bool {
+    edges(polygon).any(|(a, b)| {
+        cross(a, b, p) == T::ZERO
+            && a.x.min(b.x) <= p.x
+            && p.x <= a.x.max(b.x)
+            && a.y.min(b.y) <= p.y
+            && p.y <= a.y.max(b.y)
+    })
+}
+
+/// Every edge as a pair of vertices, including the one closing the polygon.
+#[inline]
+fn edges<T: Copy>(polygon: &[Point<T>]) -> impl Iterator<Item = (Point<T>, Point<T>)> + '_ {
+    polygon.iter().zip(polygon.iter().cycle().skip(1)).map(|(&a, &b)| (a, b))
+}
+
+/// Positive when `p` is to the left of the line from `a` to `b` with y pointing up.
+#[inline]
+fn cross<T: Signed<T>>(a: Point<T>, b: Point<T>, p: Point<T>) -> T {
+    (b.x - a.x) * (p.y - a.y) - (p.x - a.x) * (b.y - a.y)
+}
+
+#[inline]
+fn gcd<T: Signed<T>>(mut a: T, mut b: T) -> T {
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pick() {
+        // 4 by 3 rectangle with a triangle on top.
+        let polygon =
+            [ORIGIN, Point::new(4, 0), Point::new(4, 3), Point::new(2, 5), Point::new(0, 3)];
+        assert_eq!(shoelace(&polygon), 32);
+        assert_eq!(area(&polygon), 16);
+        assert_eq!(boundary_points(&polygon), 14);
+        assert_eq!(interior_points(&polygon), 10);
+        assert_eq!(lattice_points(&polygon), 24);
+
+        let reversed: Vec<_> = polygon.iter().rev().copied().collect();
+        assert_eq!(shoelace(&reversed), -32);
+        assert_eq!(lattice_points(&reversed), 24);
+    }
+
+    #[test]
+    fn locate() {
+        // U shape, open at the top between x = 2 and x = 4.
+        let polygon = [
+            ORIGIN,
+            Point::new(2, 0),
+            Point::new(2, 3),
+            Point::new(4, 3),
+            Point::new(4, 0),
+            Point::new(6, 0),
+            Point::new(6, 5),
+            Point::new(0, 5),
+        ];
+        let cases = [
+            (Point::new(1, 1), Location::Inside),
+            (Point::new(3, 1), Location::Outside),
+            (Point::new(3, 4), Location::Inside),
+            (Point::new(2, 2), Location::Boundary),
+            (Point::new(7, 0), Location::Outside),
+            (Point::new(-1, 0), Location::Outside),
+            (Point::new(1, 0), Location::Boundary),
+        ];
+        for (p, expected) in cases {
+            assert_eq!(ray_cast(&polygon, p), expected, "{p:?}");
+            assert_eq!(winding(&polygon, p), expected, "{p:?}");
+        }
+
+        let inside = |a, b| rect_inside(&polygon, &Rect::from_corners(a, b));
+        assert!(inside(ORIGIN, Point::new(2, 5)));
+        assert!(inside(Point::new(0, 3), Point::new(6, 5)));
+        assert!(!inside(ORIGIN, Point::new(6, 5)));
+        // Exactly fills the notch, touching the polygon only along its edges.
+        assert!(!inside(Point::new(2, 0), Point::new(4, 3)));
+        // Lines along the bottom edge and across the notch.
+        assert!(inside(ORIGIN, Point::new(2, 0)));
+        assert!(!inside(ORIGIN, Point::new(6, 0)));
+        assert!(inside(Point::new(0, 3), Point::new(6, 3)));
+        assert!(inside(Point::new(3, 3), Point::new(3, 3)));
+        assert!(!inside(Point::new(3, 2), Point::new(3, 2)));
+    }
+}
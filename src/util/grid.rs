@@ -1,7 +1,14 @@
+use crate::util::hash::{FastSet, FastSetBuilder as _};
 use crate::util::point::*;
+use crate::util::render::Glyph;
+use std::convert::Infallible;
+use std::fmt;
+use std::hash::Hash;
+use std::iter::StepBy;
 use std::ops::{Index, IndexMut};
+use std::slice::{ChunksExact, ChunksExactMut, Iter, IterMut};
 
-#[derive(Clone, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Grid<T> {
     pub width: i32,
     pub height: i32,
@@ -9,26 +16,111 @@ pub struct Grid<T> {
 }
 
 impl Grid<u8> {
+    /// Parses a rectangular grid of bytes, panicking with the reason if the input is malformed.
     #[inline]
     pub fn parse(input: &str) -> Self {
-        let raw: Vec<_> = input.lines().map(str::as_bytes).collect();
+        Grid::try_parse(input).unwrap_or_else(|e| panic!("{e}"))
+    }
 
-        let width = raw[0].len() as i32;
-        let height = raw.len() as i32;
-        let bytes = raw.concat();
+    #[inline]
+    pub fn try_parse(input: &str) -> Result<Self, GridError<Infallible>> {
+        Grid::parse_with(input, Ok)
+    }
 
-        Grid { width, height, bytes }
+    /// Parses a grid where lines may be shorter than the widest line, filling the missing
+    /// cells on the right with `fill`.
+    #[inline]
+    pub fn parse_padded(input: &str, fill: u8) -> Result<Self, GridError<Infallible>> {
+        Grid::parse_padded_with(input, fill, Ok)
     }
+}
 
+impl<T: Glyph> Grid<T> {
     pub fn print(&self) {
+        println!("{self}\n");
+    }
+}
+
+/// Rows separated by newlines, without a trailing newline.
+impl<T: Glyph> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // By row index rather than `rows`, which can't split a grid of zero width.
         for y in 0..self.height {
-            for x in 0..self.width {
-                let point = Point::new(x, y);
-                print!("{}", self[point] as char);
+            if y > 0 {
+                writeln!(f)?;
+            }
+            let line: String = self.row(y).iter().map(Glyph::glyph).collect();
+            f.write_str(&line)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> Grid<T> {
+    /// Parses each byte of the input into a cell with `f`. The first cell that fails to convert
+    /// is reported together with its coordinates. Every line must be as wide as the first one,
+    /// trailing blank lines are ignored.
+    #[inline]
+    pub fn parse_with<E>(
+        input: &str,
+        f: impl FnMut(u8) -> Result<T, E>,
+    ) -> Result<Grid<T>, GridError<E>> {
+        Grid::parse_lines(input, None, f)
+    }
+
+    /// Same as [`Grid::parse_with`], but lines shorter than the widest line are padded on the
+    /// right with cells converted from `fill` instead of being rejected.
+    #[inline]
+    pub fn parse_padded_with<E>(
+        input: &str,
+        fill: u8,
+        f: impl FnMut(u8) -> Result<T, E>,
+    ) -> Result<Grid<T>, GridError<E>> {
+        Grid::parse_lines(input, Some(fill), f)
+    }
+
+    fn parse_lines<E>(
+        input: &str,
+        fill: Option<u8>,
+        mut f: impl FnMut(u8) -> Result<T, E>,
+    ) -> Result<Grid<T>, GridError<E>> {
+        let mut raw: Vec<_> = input.lines().map(str::as_bytes).collect();
+        while raw.last().is_some_and(|line| line.is_empty()) {
+            raw.pop();
+        }
+        if raw.is_empty() {
+            return Err(GridError::Empty);
+        }
+
+        let width = match fill {
+            Some(_) => raw.iter().map(|line| line.len()).max().unwrap(),
+            None => raw[0].len(),
+        };
+        let height = raw.len();
+        let mut bytes = Vec::with_capacity(width * height);
+
+        for (y, line) in raw.iter().enumerate() {
+            if fill.is_none() && line.len() != width {
+                return Err(GridError::Ragged { line: y + 1, expected: width, actual: line.len() });
+            }
+
+            let padding = fill.into_iter().cycle().take(width - line.len());
+            for (x, byte) in line.iter().copied().chain(padding).enumerate() {
+                let point = Point::new(x as i32, y as i32);
+                let cell = f(byte).map_err(|error| GridError::Cell { point, byte, error })?;
+                bytes.push(cell);
             }
-            println!();
         }
-        println!();
+
+        Ok(Grid { width: width as i32, height: height as i32, bytes })
+    }
+}
+
+impl<T: TryFrom<u8>> Grid<T> {
+    /// Parses a grid of cells declared with [`cells!`].
+    #[inline]
+    pub fn parse_cells(input: &str) -> Result<Grid<T>, GridError<T::Error>> {
+        Grid::parse_with(input, T::try_from)
     }
 }
 
@@ -47,6 +139,62 @@ impl<T: Copy> Grid<T> {
     pub fn new(width: i32, height: i32, value: T) -> Grid<T> {
         Grid { width, height, bytes: vec![value; (width * height) as usize] }
     }
+
+    /// Swaps rows and columns, mirroring along the main diagonal.
+    pub fn transpose(&self) -> Grid<T> {
+        self.remap(self.height, self.width, |x, y| Point::new(y, x))
+    }
+
+    pub fn rotate_cw(&self) -> Grid<T> {
+        self.remap(self.height, self.width, |x, y| Point::new(y, self.height - 1 - x))
+    }
+
+    pub fn rotate_ccw(&self) -> Grid<T> {
+        self.remap(self.height, self.width, |x, y| Point::new(self.width - 1 - y, x))
+    }
+
+    /// Mirrors left to right.
+    pub fn flip_h(&self) -> Grid<T> {
+        self.remap(self.width, self.height, |x, y| Point::new(self.width - 1 - x, y))
+    }
+
+    /// Mirrors top to bottom.
+    pub fn flip_v(&self) -> Grid<T> {
+        self.remap(self.width, self.height, |x, y| Point::new(x, self.height - 1 - y))
+    }
+
+    /// Builds a new grid where each cell is copied from the point of `self` returned by `f`.
+    fn remap(&self, width: i32, height: i32, f: impl Fn(i32, i32) -> Point) -> Grid<T> {
+        let mut bytes = Vec::with_capacity((width * height) as usize);
+        for y in 0..height {
+            for x in 0..width {
+                bytes.push(self[f(x, y)]);
+            }
+        }
+        Grid { width, height, bytes }
+    }
+}
+
+impl<T: Copy + Eq + Hash> Grid<T> {
+    /// The distinct rotations and reflections of the grid, starting with the grid itself.
+    /// Yields up to 8 grids, fewer if the grid is symmetric.
+    pub fn orientations(&self) -> impl Iterator<Item = Grid<T>> {
+        let mut seen = FastSet::with_capacity(8);
+        let mut orientations = Vec::with_capacity(8);
+        let mut rotated = self.clone();
+
+        for _ in 0..4 {
+            let flipped = rotated.flip_h();
+            let next = rotated.rotate_cw();
+            for grid in [rotated, flipped] {
+                if seen.insert(grid.clone()) {
+                    orientations.push(grid);
+                }
+            }
+            rotated = next;
+        }
+        orientations.into_iter()
+    }
 }
 
 impl<T> Grid<T> {
@@ -64,9 +212,173 @@ impl<T> Grid<T> {
         point.x >= 0 && point.x < self.width && point.y >= 0 && point.y < self.height
     }
 
+    #[inline]
     pub fn get(&self, p: Point) -> Option<&T> {
-        let index = self.width * p.y + p.x;
-        self.bytes.get(index as usize)
+        self.contains(p).then(|| &self[p])
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.contains(p).then(|| &mut self[p])
+    }
+
+    /// Wraps a point around the edges so that the grid behaves like a torus.
+    #[inline]
+    pub fn wrap(&self, p: Point) -> Point {
+        Point::new(p.x.rem_euclid(self.width), p.y.rem_euclid(self.height))
+    }
+
+    /// Neighbors of `p` at each of the `offsets` that are inside the grid.
+    #[inline]
+    pub fn neighbors<'a>(
+        &'a self,
+        p: Point,
+        offsets: &'a [Point],
+    ) -> impl Iterator<Item = (Point, &'a T)> + 'a {
+        offsets.iter().filter_map(move |&o| self.get(p + o).map(|value| (p + o, value)))
+    }
+
+    /// Orthogonal neighbors of `p` that are inside the grid.
+    #[inline]
+    pub fn neighbors4(&self, p: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.neighbors(p, &ORTHOGONAL)
+    }
+
+    /// Orthogonal and diagonal neighbors of `p` that are inside the grid.
+    #[inline]
+    pub fn neighbors8(&self, p: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.neighbors(p, &DIAGONAL)
+    }
+
+    /// Neighbors of `p` at each of the `offsets`, wrapping around the edges.
+    #[inline]
+    pub fn neighbors_wrapping<'a>(
+        &'a self,
+        p: Point,
+        offsets: &'a [Point],
+    ) -> impl Iterator<Item = (Point, &'a T)> + 'a {
+        offsets.iter().map(move |&o| {
+            let next = self.wrap(p + o);
+            (next, &self[next])
+        })
+    }
+
+    #[inline]
+    pub fn neighbors4_wrapping(&self, p: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.neighbors_wrapping(p, &ORTHOGONAL)
+    }
+
+    #[inline]
+    pub fn neighbors8_wrapping(&self, p: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.neighbors_wrapping(p, &DIAGONAL)
+    }
+
+    /// Neighbors of `p` at each of the `offsets`, with cells outside the grid reading as
+    /// `border`. Always yields one item per offset.
+    #[inline]
+    pub fn neighbors_or<'a>(
+        &'a self,
+        p: Point,
+        offsets: &'a [Point],
+        border: &'a T,
+    ) -> impl Iterator<Item = (Point, &'a T)> + 'a {
+        offsets.iter().map(move |&o| (p + o, self.get(p + o).unwrap_or(border)))
+    }
+
+    #[inline]
+    pub fn neighbors4_or<'a>(
+        &'a self,
+        p: Point,
+        border: &'a T,
+    ) -> impl Iterator<Item = (Point, &'a T)> + 'a {
+        self.neighbors_or(p, &ORTHOGONAL, border)
+    }
+
+    #[inline]
+    pub fn neighbors8_or<'a>(
+        &'a self,
+        p: Point,
+        border: &'a T,
+    ) -> impl Iterator<Item = (Point, &'a T)> + 'a {
+        self.neighbors_or(p, &DIAGONAL, border)
+    }
+
+    #[inline]
+    pub fn row(&self, y: i32) -> &[T] {
+        let start = (self.width * y) as usize;
+        &self.bytes[start..start + self.width as usize]
+    }
+
+    #[inline]
+    pub fn row_mut(&mut self, y: i32) -> &mut [T] {
+        assert!(0 <= y && y < self.height, "Row {y} out of bounds");
+        let start = (self.width * y) as usize;
+        &mut self.bytes[start..start + self.width as usize]
+    }
+
+    #[inline]
+    pub fn rows(&self) -> ChunksExact<'_, T> {
+        self.bytes.chunks_exact(self.width as usize)
+    }
+
+    #[inline]
+    pub fn rows_mut(&mut self) -> ChunksExactMut<'_, T> {
+        self.bytes.chunks_exact_mut(self.width as usize)
+    }
+
+    /// Cells of column `x` from top to bottom.
+    #[inline]
+    pub fn column(&self, x: i32) -> StepBy<Iter<'_, T>> {
+        assert!(0 <= x && x < self.width, "Column {x} out of bounds");
+        self.bytes[x as usize..].iter().step_by(self.width as usize)
+    }
+
+    #[inline]
+    pub fn column_mut(&mut self, x: i32) -> StepBy<IterMut<'_, T>> {
+        assert!(0 <= x && x < self.width, "Column {x} out of bounds");
+        self.bytes[x as usize..].iter_mut().step_by(self.width as usize)
+    }
+
+    /// Columns from left to right, each iterating its cells from top to bottom.
+    #[inline]
+    pub fn columns(&self) -> impl DoubleEndedIterator<Item = StepBy<Iter<'_, T>>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Mutable columns from left to right. Each column is collected into a `Vec` of references
+    /// so that all columns can be borrowed at the same time.
+    pub fn columns_mut(&mut self) -> impl DoubleEndedIterator<Item = Vec<&mut T>> {
+        let mut columns: Vec<_> =
+            (0..self.width).map(|_| Vec::with_capacity(self.height as usize)).collect();
+        for row in self.bytes.chunks_exact_mut(self.width as usize) {
+            for (column, cell) in columns.iter_mut().zip(row) {
+                column.push(cell);
+            }
+        }
+        columns.into_iter()
+    }
+
+    /// Borrowed view of the `width` by `height` rectangle with top left corner at `origin`.
+    #[inline]
+    pub fn subgrid(&self, origin: Point, width: i32, height: i32) -> SubGrid<'_, T> {
+        self.check_subgrid(origin, width, height);
+        SubGrid { grid: self, origin, width, height }
+    }
+
+    #[inline]
+    pub fn subgrid_mut(&mut self, origin: Point, width: i32, height: i32) -> SubGridMut<'_, T> {
+        self.check_subgrid(origin, width, height);
+        SubGridMut { grid: self, origin, width, height }
+    }
+
+    fn check_subgrid(&self, origin: Point, width: i32, height: i32) {
+        let inside = width >= 0
+            && height >= 0
+            && self.contains(origin)
+            && origin.x + width <= self.width
+            && origin.y + height <= self.height;
+        let empty = width * height == 0;
+        assert!(inside || empty, "Subgrid {width}x{height} at {origin:?} out of bounds");
     }
 }
 
@@ -84,4 +396,312 @@ impl<T> IndexMut<Point> for Grid<T> {
     fn index_mut(&mut self, index: Point) -> &mut Self::Output {
         &mut self.bytes[(self.width * index.y + index.x) as usize]
     }
-}
\ No newline at end of file
+}
+
+/// Rectangular view into a [`Grid`]. Points are relative to the top left corner of the view.
+pub struct SubGrid<'a, T> {
+    grid: &'a Grid<T>,
+    pub origin: Point,
+    pub width: i32,
+    pub height: i32,
+}
+
+/// Mutable rectangular view into a [`Grid`]. Points are relative to the top left corner of
+/// the view.
+pub struct SubGridMut<'a, T> {
+    grid: &'a mut Grid<T>,
+    pub origin: Point,
+    pub width: i32,
+    pub height: i32,
+}
+
+macro_rules! subgrid {
+    ($name:ident) => {
+        impl<T> $name<'_, T> {
+            #[inline]
+            pub fn contains(&self, point: Point) -> bool {
+                point.x >= 0 && point.x < self.width && point.y >= 0 && point.y < self.height
+            }
+
+            #[inline]
+            pub fn get(&self, p: Point) -> Option<&T> {
+                self.contains(p).then(|| &self.grid[self.origin + p])
+            }
+
+            #[inline]
+            pub fn row(&self, y: i32) -> &[T] {
+                assert!(0 <= y && y < self.height, "Row {y} out of bounds");
+                let start = self.origin.x as usize;
+                &self.grid.row(self.origin.y + y)[start..start + self.width as usize]
+            }
+
+            #[inline]
+            pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+                (0..self.height).map(|y| self.row(y))
+            }
+
+            #[inline]
+            pub fn column(&self, x: i32) -> impl Iterator<Item = &T> {
+                assert!(0 <= x && x < self.width, "Column {x} out of bounds");
+                (0..self.height).map(move |y| &self[Point::new(x, y)])
+            }
+
+            /// All cells with their position relative to the view, left to right and top to
+            /// bottom.
+            #[inline]
+            pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+                (0..self.height).flat_map(move |y| {
+                    self.row(y).iter().enumerate().map(move |(x, v)| (Point::new(x as i32, y), v))
+                })
+            }
+        }
+
+        impl<T> Index<Point> for $name<'_, T> {
+            type Output = T;
+
+            #[inline]
+            fn index(&self, index: Point) -> &Self::Output {
+                assert!(self.contains(index), "{index:?} out of bounds");
+                &self.grid[self.origin + index]
+            }
+        }
+    };
+}
+
+subgrid!(SubGrid);
+subgrid!(SubGridMut);
+
+impl<T> SubGridMut<'_, T> {
+    #[inline]
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.contains(p).then(|| &mut self.grid[self.origin + p])
+    }
+
+    #[inline]
+    pub fn row_mut(&mut self, y: i32) -> &mut [T] {
+        assert!(0 <= y && y < self.height, "Row {y} out of bounds");
+        let start = self.origin.x as usize;
+        &mut self.grid.row_mut(self.origin.y + y)[start..start + self.width as usize]
+    }
+
+    #[inline]
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        let (x, y) = (self.origin.x as usize, self.origin.y as usize);
+        let (width, height) = (self.width as usize, self.height as usize);
+        self.grid.rows_mut().skip(y).take(height).map(move |row| &mut row[x..x + width])
+    }
+
+    #[inline]
+    pub fn column_mut(&mut self, x: i32) -> impl Iterator<Item = &mut T> {
+        let (start, height) = (self.origin.x + x, self.height as usize);
+        assert!(0 <= x && x < self.width, "Column {x} out of bounds");
+        self.grid.column_mut(start).skip(self.origin.y as usize).take(height)
+    }
+
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        for row in self.rows_mut() {
+            row.fill(value.clone());
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for SubGridMut<'_, T> {
+    #[inline]
+    fn index_mut(&mut self, index: Point) -> &mut Self::Output {
+        assert!(self.contains(index), "{index:?} out of bounds");
+        &mut self.grid[self.origin + index]
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GridError<E> {
+    /// The input has no lines.
+    Empty,
+    /// Line `line` (starting from 1) doesn't have the `expected` number of columns.
+    Ragged { line: usize, expected: usize, actual: usize },
+    /// The byte at `point` could not be converted into a cell.
+    Cell { point: Point, byte: u8, error: E },
+}
+
+impl<E: fmt::Display> fmt::Display for GridError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::Empty => write!(f, "Grid input is empty"),
+            GridError::Ragged { line, expected, actual } => {
+                write!(f, "Line {line} has {actual} columns, expected {expected}")
+            }
+            GridError::Cell { point, byte, error } => write!(
+                f,
+                "Invalid cell {:?} at ({}, {}): {error}",
+                *byte as char, point.x, point.y
+            ),
+        }
+    }
+}
+
+/// Error returned by the [`TryFrom<u8>`] implementation generated by [`cells!`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnknownCell(pub u8);
+
+impl fmt::Display for UnknownCell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown character {:?}", self.0 as char)
+    }
+}
+
+/// Declares an enum whose variants map to characters of an input grid, for example:
+///
+/// ```none
+/// cells! {
+///     enum Tile {
+///         Empty = b'.' | b' ',
+///         Wall = b'#',
+///     }
+/// }
+/// ```
+///
+/// Generates [`TryFrom<u8>`] so the enum can be loaded with [`Grid::parse_cells`], and
+/// `From<Tile> for u8` which maps each variant back to its first listed byte. The same byte is
+/// used as the [`Glyph`], so grids of the enum can be printed.
+macro_rules! cells {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $($variant:ident = $byte:literal $(| $alt:literal)*),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        $vis enum $name {
+            $($variant),+
+        }
+
+        impl TryFrom<u8> for $name {
+            type Error = $crate::util::grid::UnknownCell;
+
+            #[inline]
+            fn try_from(byte: u8) -> Result<Self, Self::Error> {
+                match byte {
+                    $($byte $(| $alt)* => Ok($name::$variant),)+
+                    _ => Err($crate::util::grid::UnknownCell(byte)),
+                }
+            }
+        }
+
+        impl From<$name> for u8 {
+            #[inline]
+            fn from(cell: $name) -> u8 {
+                match cell {
+                    $($name::$variant => $byte),+
+                }
+            }
+        }
+
+        impl $crate::util::render::Glyph for $name {
+            #[inline]
+            fn glyph(&self) -> char {
+                u8::from(*self) as char
+            }
+        }
+    };
+}
+
+pub(crate) use cells;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    cells! {
+        enum Tile {
+            Open = b'.',
+            Wall = b'#' | b'@',
+        }
+    }
+
+    #[test]
+    fn parse_cells() {
+        let grid: Grid<Tile> = Grid::parse_cells(".#\n@.").unwrap();
+        assert_eq!(grid.bytes, [Tile::Open, Tile::Wall, Tile::Wall, Tile::Open]);
+        assert_eq!(u8::from(grid[Point::new(0, 1)]), b'#');
+
+        let error = Grid::<Tile>::parse_cells("..\n.x").unwrap_err();
+        let expected =
+            GridError::Cell { point: Point::new(1, 1), byte: b'x', error: UnknownCell(b'x') };
+        assert_eq!(error, expected);
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = Grid::parse("abc\ndef");
+        let cells = |iter: &mut dyn Iterator<Item = (Point, &u8)>| -> Vec<u8> {
+            iter.map(|(_, &b)| b).collect()
+        };
+
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(-1, 1)), None);
+        assert_eq!(cells(&mut grid.neighbors4(Point::new(0, 0))), b"db");
+        assert_eq!(cells(&mut grid.neighbors8(Point::new(1, 1))), b"abcdf");
+        assert_eq!(cells(&mut grid.neighbors4_wrapping(Point::new(0, 0))), b"ddcb");
+        assert_eq!(cells(&mut grid.neighbors4_or(Point::new(2, 1), &b'#')), b"c#e#");
+    }
+
+    #[test]
+    fn views() {
+        let mut grid = Grid::parse("abcd\nefgh\nijkl");
+
+        assert_eq!(grid.row(1), b"efgh");
+        assert_eq!(grid.rows().last().unwrap(), b"ijkl");
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), b"cgk");
+        assert_eq!(grid.columns().rev().flatten().copied().collect::<Vec<_>>(), b"dhlcgkbfjaei");
+
+        let sub = grid.subgrid(Point::new(1, 1), 2, 2);
+        assert_eq!(sub.rows().collect::<Vec<_>>(), [b"fg", b"jk"]);
+        assert_eq!(sub.column(1).copied().collect::<Vec<_>>(), b"gk");
+        assert_eq!(sub.get(Point::new(2, 0)), None);
+
+        grid.subgrid_mut(Point::new(2, 0), 2, 2).fill(b'.');
+        for cell in grid.column_mut(0) {
+            *cell = b'#';
+        }
+        for (x, column) in grid.columns_mut().enumerate() {
+            *column.into_iter().last().unwrap() = b'0' + x as u8;
+        }
+        assert_eq!(grid.bytes, b"#b..#f..0123");
+    }
+
+    #[test]
+    fn transforms() {
+        let grid = Grid::parse("ab\ncd\nef");
+
+        assert_eq!(grid.transpose(), Grid::parse("ace\nbdf"));
+        assert_eq!(grid.rotate_cw(), Grid::parse("eca\nfdb"));
+        assert_eq!(grid.rotate_ccw(), Grid::parse("bdf\nace"));
+        assert_eq!(grid.flip_h(), Grid::parse("ba\ndc\nfe"));
+        assert_eq!(grid.flip_v(), Grid::parse("ef\ncd\nab"));
+        assert_eq!(grid.rotate_cw().rotate_cw(), grid.flip_h().flip_v());
+
+        assert_eq!(grid.orientations().count(), 8);
+        assert_eq!(Grid::parse("##\n#.").orientations().count(), 4);
+        assert_eq!(Grid::parse("#.#\n###").orientations().count(), 4);
+        assert_eq!(Grid::parse("##\n##").orientations().count(), 1);
+        assert_eq!(Grid::parse("##.\n.##").orientations().count(), 4);
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(Grid::try_parse("ab\r\ncd\r\n\n").unwrap().bytes, b"abcd");
+        assert_eq!(Grid::try_parse("\n"), Err(GridError::Empty));
+        let ragged = GridError::Ragged { line: 3, expected: 2, actual: 1 };
+        assert_eq!(Grid::try_parse("ab\ncd\ne\nfg"), Err(ragged));
+        let ragged = GridError::Ragged { line: 2, expected: 2, actual: 0 };
+        assert_eq!(Grid::try_parse("ab\n\ncd"), Err(ragged));
+
+        let padded = Grid::parse_padded(" 1\n23 \n4", b'.').unwrap();
+        assert_eq!((padded.width, padded.height), (3, 3));
+        assert_eq!(padded.bytes, b" 1.23 4..");
+    }
+}
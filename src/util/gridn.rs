@@ -0,0 +1,248 @@
+//! Dense grid with any number of dimensions, for 3D and 4D automata and voxel puzzles.
+//!
+//! Points are arrays `[i32; D]`. Cells are stored with the first axis varying fastest, so a
+//! `GridN<T, 2>` has the same layout as a [`Grid<T>`] with axis 0 as x and axis 1 as y.
+//!
+//! Neighbors come in two flavours:
+//! * Orthogonal, the `2 * D` cells differing by one along a single axis.
+//! * Moore, the `3^D - 1` cells differing by at most one along every axis.
+use crate::util::grid::Grid;
+use crate::util::point::*;
+use std::ops::{Index, IndexMut};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct GridN<T, const D: usize> {
+    pub dims: [i32; D],
+    strides: [usize; D],
+    pub cells: Vec<T>,
+}
+
+impl<T: Clone, const D: usize> GridN<T, D> {
+    pub fn new(dims: [i32; D], value: T) -> Self {
+        let strides = strides(dims);
+        let len = dims.iter().map(|&d| d as usize).product();
+        GridN { dims, strides, cells: vec![value; len] }
+    }
+
+    /// Embeds a 2D grid in the plane where every axis after the first two is zero, with all
+    /// other axes one cell long.
+    pub fn from_grid(grid: &Grid<T>) -> Self {
+        assert!(D >= 2, "A {D} dimensional grid can't hold a 2D grid");
+        let mut dims = [1; D];
+        dims[0] = grid.width;
+        dims[1] = grid.height;
+        GridN { dims, strides: strides(dims), cells: grid.bytes.clone() }
+    }
+
+    /// Cells where axis `axis` equals `index`, as a grid with one dimension less. `E` must be
+    /// `D - 1`, remaining axes keep their order.
+    pub fn slice<const E: usize>(&self, axis: usize, index: i32) -> GridN<T, E> {
+        assert!(E + 1 == D, "Slice of a {D} dimensional grid has {} dimensions, not {E}", D - 1);
+        assert!(axis < D && 0 <= index && index < self.dims[axis], "Slice out of bounds");
+
+        let mut dims = [0; E];
+        for (to, from) in (0..D).filter(|&a| a != axis).enumerate() {
+            dims[to] = self.dims[from];
+        }
+
+        let cells = self
+            .points()
+            .filter(|p| p[axis] == index)
+            .map(|p| self.cells[self.offset(p)].clone())
+            .collect();
+        GridN { dims, strides: strides(dims), cells }
+    }
+}
+
+impl<T, const D: usize> GridN<T, D> {
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    #[inline]
+    pub fn contains(&self, p: [i32; D]) -> bool {
+        p.iter().zip(&self.dims).all(|(&c, &d)| 0 <= c && c < d)
+    }
+
+    #[inline]
+    pub fn get(&self, p: [i32; D]) -> Option<&T> {
+        self.contains(p).then(|| &self.cells[self.offset(p)])
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, p: [i32; D]) -> Option<&mut T> {
+        if self.contains(p) {
+            let offset = self.offset(p);
+            Some(&mut self.cells[offset])
+        } else {
+            None
+        }
+    }
+
+    /// Every point of the grid, in storage order.
+    pub fn points(&self) -> impl Iterator<Item = [i32; D]> + '_ {
+        (0..self.len()).map(|offset| self.point(offset))
+    }
+
+    /// Neighbors of `p` at each of the `offsets` that are inside the grid.
+    #[inline]
+    pub fn neighbors<'a>(
+        &'a self,
+        p: [i32; D],
+        offsets: &'a [[i32; D]],
+    ) -> impl Iterator<Item = ([i32; D], &'a T)> + 'a {
+        offsets.iter().filter_map(move |&o| {
+            let next = add(p, o);
+            self.get(next).map(|value| (next, value))
+        })
+    }
+
+    /// Orthogonal neighbors of `p` that are inside the grid.
+    pub fn neighbors_orthogonal(&self, p: [i32; D]) -> impl Iterator<Item = ([i32; D], &T)> {
+        orthogonal_offsets::<D>().filter_map(move |o| {
+            let next = add(p, o);
+            self.get(next).map(|value| (next, value))
+        })
+    }
+
+    /// Moore neighbors of `p` that are inside the grid.
+    pub fn neighbors_moore(&self, p: [i32; D]) -> impl Iterator<Item = ([i32; D], &T)> {
+        moore_offsets::<D>().filter_map(move |o| {
+            let next = add(p, o);
+            self.get(next).map(|value| (next, value))
+        })
+    }
+
+    #[inline]
+    fn offset(&self, p: [i32; D]) -> usize {
+        assert!(self.contains(p), "{p:?} out of bounds");
+        p.iter().zip(&self.strides).map(|(&c, &s)| c as usize * s).sum()
+    }
+
+    #[inline]
+    fn point(&self, mut offset: usize) -> [i32; D] {
+        let mut p = [0; D];
+        for (c, &d) in p.iter_mut().zip(&self.dims) {
+            *c = (offset % d as usize) as i32;
+            offset /= d as usize;
+        }
+        p
+    }
+}
+
+impl<T, const D: usize> Index<[i32; D]> for GridN<T, D> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: [i32; D]) -> &Self::Output {
+        &self.cells[self.offset(index)]
+    }
+}
+
+impl<T, const D: usize> IndexMut<[i32; D]> for GridN<T, D> {
+    #[inline]
+    fn index_mut(&mut self, index: [i32; D]) -> &mut Self::Output {
+        let offset = self.offset(index);
+        &mut self.cells[offset]
+    }
+}
+
+impl<T: Clone> From<&Grid<T>> for GridN<T, 2> {
+    fn from(grid: &Grid<T>) -> Self {
+        GridN::from_grid(grid)
+    }
+}
+
+impl<T> From<GridN<T, 2>> for Grid<T> {
+    fn from(grid: GridN<T, 2>) -> Self {
+        let [width, height] = grid.dims;
+        Grid { width, height, bytes: grid.cells }
+    }
+}
+
+impl<T> Index<Point> for GridN<T, 2> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: Point) -> &Self::Output {
+        &self[[index.x, index.y]]
+    }
+}
+
+/// The `2 * D` offsets to orthogonal neighbors.
+pub fn orthogonal_offsets<const D: usize>() -> impl Iterator<Item = [i32; D]> {
+    (0..D).flat_map(|axis| {
+        [-1, 1].map(|delta| {
+            let mut offset = [0; D];
+            offset[axis] = delta;
+            offset
+        })
+    })
+}
+
+/// The `3^D - 1` offsets to Moore neighbors, each the digits of a base 3 number minus one.
+pub fn moore_offsets<const D: usize>() -> impl Iterator<Item = [i32; D]> {
+    (0..3_usize.pow(D as u32))
+        .map(|mut n| {
+            let mut offset = [0; D];
+            for c in &mut offset {
+                *c = (n % 3) as i32 - 1;
+                n /= 3;
+            }
+            offset
+        })
+        .filter(|offset| offset.iter().any(|&c| c != 0))
+}
+
+#[inline]
+fn add<const D: usize>(mut p: [i32; D], o: [i32; D]) -> [i32; D] {
+    for (c, d) in p.iter_mut().zip(o) {
+        *c += d;
+    }
+    p
+}
+
+#[inline]
+fn strides<const D: usize>(dims: [i32; D]) -> [usize; D] {
+    let mut strides = [0; D];
+    let mut stride = 1;
+    for (s, &d) in strides.iter_mut().zip(&dims) {
+        assert!(d >= 0, "Negative dimension in {dims:?}");
+        *s = stride;
+        stride *= d as usize;
+    }
+    strides
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gridn() {
+        let grid = Grid::parse(".#.\n..#\n###");
+        let mut cube = GridN::<u8, 3>::from_grid(&grid);
+        assert_eq!(cube.dims, [3, 3, 1]);
+        assert_eq!(cube[[1, 0, 0]], b'#');
+        assert_eq!(cube.get([1, 0, 1]), None);
+
+        let center = [1, 1, 0];
+        assert_eq!(cube.neighbors_orthogonal(center).count(), 4);
+        let live = cube.neighbors_moore(center).filter(|&(_, &c)| c == b'#').count();
+        assert_eq!(live, 5);
+        assert_eq!(orthogonal_offsets::<4>().count(), 8);
+        assert_eq!(moore_offsets::<4>().count(), 80);
+
+        cube[[2, 0, 0]] = b'@';
+        let row: GridN<u8, 2> = cube.slice(1, 0);
+        assert_eq!((row.dims, row.cells), ([3, 1], b".#@".to_vec()));
+        let plane: GridN<u8, 2> = cube.slice(2, 0);
+        assert_eq!(Grid::from(plane), Grid::parse(".#@\n..#\n###"));
+    }
+}
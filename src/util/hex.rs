@@ -0,0 +1,221 @@
+//! Hexagonal grids in [axial coordinates].
+//!
+//! A [`Hex`] stores two coordinates `q` and `r`, the third cube coordinate `s` is implied by
+//! `q + r + s = 0`. Distances, rotations, rings and spirals follow the usual cube coordinate
+//! formulas, with y pointing down like the rest of the grids here.
+//!
+//! Puzzles name directions depending on how the hexagons are drawn. With [`HexLayout::Flat`]
+//! hexagons have a flat top and neighbors `n`, `ne`, `se`, `s`, `sw` and `nw`. With
+//! [`HexLayout::Pointy`] hexagons have a pointy top and neighbors `e`, `ne`, `nw`, `w`, `sw` and
+//! `se`. Both layouts map onto the same [`HEX_DIRECTIONS`], so only parsing differs.
+//!
+//! [axial coordinates]: https://www.redblobgames.com/grids/hexagons/#coordinates-axial
+use crate::util::direction::UnknownDirection;
+use crate::util::point3::Point3;
+use std::ops::{Add, AddAssign, Mul, Sub, SubAssign};
+
+pub const HEX_ORIGIN: Hex = Hex::new(0, 0);
+/// The six neighbors, counter clockwise starting from the one along positive `q`.
+pub const HEX_DIRECTIONS: [Hex; 6] = [
+    Hex::new(1, 0),
+    Hex::new(1, -1),
+    Hex::new(0, -1),
+    Hex::new(-1, 0),
+    Hex::new(-1, 1),
+    Hex::new(0, 1),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Hex {
+    pub q: i32,
+    pub r: i32,
+}
+
+impl Hex {
+    #[inline]
+    #[must_use]
+    pub const fn new(q: i32, r: i32) -> Self {
+        Hex { q, r }
+    }
+
+    /// The implied third cube coordinate.
+    #[inline]
+    pub const fn s(self) -> i32 {
+        -self.q - self.r
+    }
+
+    #[inline]
+    pub const fn cube(self) -> Point3 {
+        Point3::new(self.q, self.r, self.s())
+    }
+
+    #[inline]
+    pub fn neighbors(self) -> [Hex; 6] {
+        HEX_DIRECTIONS.map(|d| self + d)
+    }
+
+    /// Fewest steps between two hexes.
+    #[inline]
+    pub fn distance(self, other: Self) -> i32 {
+        let d = (self - other).cube();
+        (d.x.abs() + d.y.abs() + d.z.abs()) / 2
+    }
+
+    /// Turns 60 degrees clockwise around the origin.
+    #[inline]
+    #[must_use]
+    pub fn clockwise(self) -> Self {
+        Hex::new(-self.r, -self.s())
+    }
+
+    /// Turns 60 degrees counter clockwise around the origin.
+    #[inline]
+    #[must_use]
+    pub fn counter_clockwise(self) -> Self {
+        Hex::new(-self.s(), -self.q)
+    }
+
+    /// Hexes exactly `radius` steps away, going counter clockwise. A radius of zero is just
+    /// the hex itself.
+    pub fn ring(self, radius: i32) -> Vec<Hex> {
+        if radius == 0 {
+            return vec![self];
+        }
+
+        let mut ring = Vec::with_capacity(6 * radius as usize);
+        let mut hex = self + HEX_DIRECTIONS[4] * radius;
+        for direction in HEX_DIRECTIONS {
+            for _ in 0..radius {
+                ring.push(hex);
+                hex += direction;
+            }
+        }
+        ring
+    }
+
+    /// Hexes at most `radius` steps away, ring by ring from the center outwards.
+    pub fn spiral(self, radius: i32) -> Vec<Hex> {
+        (0..=radius).flat_map(|r| self.ring(r)).collect()
+    }
+}
+
+impl Add for Hex {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Hex::new(self.q + rhs.q, self.r + rhs.r)
+    }
+}
+
+impl AddAssign for Hex {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Mul<i32> for Hex {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: i32) -> Self {
+        Hex::new(self.q * rhs, self.r * rhs)
+    }
+}
+
+impl Sub for Hex {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Hex::new(self.q - rhs.q, self.r - rhs.r)
+    }
+}
+
+impl SubAssign for Hex {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HexLayout {
+    Flat,
+    Pointy,
+}
+
+impl HexLayout {
+    /// Names of [`HEX_DIRECTIONS`] in this layout.
+    #[inline]
+    pub const fn names(self) -> [&'static str; 6] {
+        match self {
+            HexLayout::Flat => ["se", "ne", "n", "nw", "sw", "s"],
+            HexLayout::Pointy => ["e", "ne", "nw", "w", "sw", "se"],
+        }
+    }
+
+    /// Parses a single direction such as `ne`, ignoring case.
+    pub fn direction(self, name: &str) -> Result<Hex, UnknownDirection> {
+        self.names()
+            .iter()
+            .position(|n| n.eq_ignore_ascii_case(name))
+            .map(|i| HEX_DIRECTIONS[i])
+            .ok_or_else(|| UnknownDirection(name.to_string()))
+    }
+
+    /// Parses a list of directions, either separated by commas or whitespace like `ne,ne,s`, or
+    /// run together like `esenee`.
+    pub fn steps(self, input: &str) -> Result<Vec<Hex>, UnknownDirection> {
+        let mut steps = Vec::new();
+        let mut rest = input.trim_start_matches(|c: char| c == ',' || c.is_whitespace());
+
+        while !rest.is_empty() {
+            // Two letter names first, so `se` isn't read as `s` followed by `e`.
+            let (step, len) = match rest.get(..2).map(|two| self.direction(two)) {
+                Some(Ok(step)) => (step, 2),
+                _ => (self.direction(rest.get(..1).unwrap_or(rest))?, 1),
+            };
+            steps.push(step);
+            rest = rest[len..].trim_start_matches(|c: char| c == ',' || c.is_whitespace());
+        }
+
+        Ok(steps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex() {
+        let walk = |input| {
+            let steps = HexLayout::Flat.steps(input).unwrap();
+            steps.into_iter().fold(HEX_ORIGIN, |hex, step| hex + step).distance(HEX_ORIGIN)
+        };
+        assert_eq!(walk("ne,ne,ne"), 3);
+        assert_eq!(walk("ne,ne,sw,sw"), 0);
+        assert_eq!(walk("ne,ne,s,s"), 2);
+        assert_eq!(walk("se,sw,se,sw,sw"), 3);
+
+        let steps = HexLayout::Pointy.steps("nwwswee").unwrap();
+        assert_eq!(steps.len(), 5);
+        assert_eq!(steps.iter().fold(HEX_ORIGIN, |hex, &step| hex + step), HEX_ORIGIN);
+        let steps = HexLayout::Pointy.steps("e se, W").unwrap();
+        assert_eq!(steps.into_iter().fold(HEX_ORIGIN, |hex, step| hex + step), Hex::new(0, 1));
+        assert_eq!(HexLayout::Pointy.steps("n"), Err(UnknownDirection("n".to_string())));
+
+        let east = HEX_DIRECTIONS[0];
+        assert_eq!(east.clockwise(), HEX_DIRECTIONS[5]);
+        assert_eq!(east.counter_clockwise(), HEX_DIRECTIONS[1]);
+        assert_eq!(east.cube(), Point3::new(1, 0, -1));
+
+        let center = Hex::new(2, -1);
+        assert_eq!(center.ring(3).len(), 18);
+        assert!(center.ring(3).iter().all(|&hex| hex.distance(center) == 3));
+        assert_eq!(center.spiral(2).len(), 19);
+        assert_eq!(center.neighbors().iter().filter(|&&n| n.distance(center) == 1).count(), 6);
+    }
+}
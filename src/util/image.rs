@@ -0,0 +1,249 @@
+//! Export of grids as images, without any dependencies.
+//!
+//! An [`Image`] is built from a [`Grid`] by mapping each cell to a colour, optionally scaled up so
+//! that each cell covers a square of pixels. It can then be written as a binary [PPM], which is
+//! trivial but not widely supported, or as a [PNG].
+//!
+//! The PNG encoder skips compression entirely. The image data is wrapped in [zlib] "stored"
+//! blocks, so the only work is computing the CRC-32 of each chunk and the Adler-32 of the data.
+//! Files are larger than needed, but every viewer can open them.
+//!
+//! [`Frames`] writes a numbered sequence of images to a directory, one per step of a simulation,
+//! ready to be turned into an animation by an external tool.
+//!
+//! [PPM]: https://netpbm.sourceforge.net/doc/ppm.html
+//! [PNG]: https://www.w3.org/TR/png/
+//! [zlib]: https://www.rfc-editor.org/rfc/rfc1950
+use crate::util::grid::Grid;
+use crate::util::point::*;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write as _};
+use std::path::{Path, PathBuf};
+
+pub type Rgb = [u8; 3];
+
+/// Largest amount of data in a single deflate stored block.
+const MAX_STORED: usize = 65_535;
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    /// Pixels in row major order, three bytes each.
+    pub pixels: Vec<u8>,
+}
+
+impl Image {
+    /// Draws each cell of `grid` as a `scale` by `scale` square of the colour given by `colour`.
+    pub fn from_grid<T>(grid: &Grid<T>, scale: u32, colour: impl Fn(&T) -> Rgb) -> Self {
+        assert!(scale > 0, "Scale must be at least 1");
+        let width = grid.width as u32 * scale;
+        let height = grid.height as u32 * scale;
+        let mut pixels = Vec::with_capacity((width * height * 3) as usize);
+
+        for y in 0..grid.height {
+            let mut row = Vec::with_capacity(width as usize * 3);
+            for x in 0..grid.width {
+                let rgb = colour(&grid[Point::new(x, y)]);
+                for _ in 0..scale {
+                    row.extend_from_slice(&rgb);
+                }
+            }
+            for _ in 0..scale {
+                pixels.extend_from_slice(&row);
+            }
+        }
+
+        Image { width, height, pixels }
+    }
+
+    pub fn write_ppm(&self, out: &mut impl io::Write) -> io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        out.write_all(&self.pixels)
+    }
+
+    pub fn write_png(&self, out: &mut impl io::Write) -> io::Result<()> {
+        let mut header = Vec::with_capacity(13);
+        header.extend_from_slice(&self.width.to_be_bytes());
+        header.extend_from_slice(&self.height.to_be_bytes());
+        // Bit depth 8, truecolour, default compression and filter, no interlace.
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+        // Each scanline starts with its filter type, 0 for none.
+        let stride = self.width as usize * 3;
+        let mut raw = Vec::with_capacity((stride + 1) * self.height as usize);
+        for row in self.pixels.chunks_exact(stride.max(1)).take(self.height as usize) {
+            raw.push(0);
+            raw.extend_from_slice(row);
+        }
+
+        out.write_all(&PNG_SIGNATURE)?;
+        write_chunk(out, b"IHDR", &header)?;
+        write_chunk(out, b"IDAT", &zlib_stored(&raw))?;
+        write_chunk(out, b"IEND", &[])
+    }
+}
+
+fn write_chunk(out: &mut impl io::Write, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    let crc = crc32(kind.iter().chain(data));
+    out.write_all(&(data.len() as u32).to_be_bytes())?;
+    out.write_all(kind)?;
+    out.write_all(data)?;
+    out.write_all(&crc.to_be_bytes())
+}
+
+/// Wraps `data` in a zlib stream of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let blocks = data.len().div_ceil(MAX_STORED).max(1);
+    let mut stream = Vec::with_capacity(data.len() + 5 * blocks + 6);
+    // Deflate with a 32K window and the fastest level, the check bits make this divisible by 31.
+    stream.extend_from_slice(&[0x78, 0x01]);
+
+    for i in 0..blocks {
+        let block = &data[i * MAX_STORED..data.len().min((i + 1) * MAX_STORED)];
+        let len = block.len() as u16;
+        // BFINAL on the last block, BTYPE 00 for stored.
+        stream.push(u8::from(i == blocks - 1));
+        stream.extend_from_slice(&len.to_le_bytes());
+        stream.extend_from_slice(&(!len).to_le_bytes());
+        stream.extend_from_slice(block);
+    }
+
+    stream.extend_from_slice(&adler32(data).to_be_bytes());
+    stream
+}
+
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut c = n as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 == 1 { 0xedb88320 ^ (c >> 1) } else { c >> 1 };
+            k += 1;
+        }
+        table[n] = c;
+        n += 1;
+    }
+    table
+};
+
+fn crc32<'a>(bytes: impl IntoIterator<Item = &'a u8>) -> u32 {
+    let crc = bytes.into_iter().fold(u32::MAX, |crc, &b| {
+        CRC_TABLE[((crc ^ b as u32) & 0xff) as usize] ^ (crc >> 8)
+    });
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    const MOD: u32 = 65_521;
+    // 5552 is the largest run of bytes that can't overflow before taking the modulus.
+    let (mut a, mut b) = (1, 0);
+    for chunk in bytes.chunks(5552) {
+        for &byte in chunk {
+            a += byte as u32;
+            b += a;
+        }
+        a %= MOD;
+        b %= MOD;
+    }
+    (b << 16) | a
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Ppm,
+    Png,
+}
+
+impl Format {
+    fn extension(self) -> &'static str {
+        match self {
+            Format::Ppm => "ppm",
+            Format::Png => "png",
+        }
+    }
+}
+
+/// Writes images to `dir` as `prefix0000.png`, `prefix0001.png` and so on.
+pub struct Frames {
+    dir: PathBuf,
+    prefix: String,
+    format: Format,
+    next: usize,
+}
+
+impl Frames {
+    /// Creates `dir` if needed. Existing frames with the same prefix are overwritten.
+    pub fn new(dir: impl AsRef<Path>, prefix: &str, format: Format) -> io::Result<Self> {
+        fs::create_dir_all(&dir)?;
+        let dir = dir.as_ref().to_path_buf();
+        Ok(Frames { dir, prefix: prefix.to_string(), format, next: 0 })
+    }
+
+    /// Writes the next frame, returning its path.
+    pub fn write(&mut self, image: &Image) -> io::Result<PathBuf> {
+        let name = format!("{}{:04}.{}", self.prefix, self.next, self.format.extension());
+        let path = self.dir.join(name);
+        let mut out = BufWriter::new(File::create(&path)?);
+
+        match self.format {
+            Format::Ppm => image.write_ppm(&mut out)?,
+            Format::Png => image.write_png(&mut out)?,
+        }
+        out.flush()?;
+
+        self.next += 1;
+        Ok(path)
+    }
+
+    /// Number of frames written so far.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.next
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.next == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"IEND"), 0xae426082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e60398);
+
+        let big = vec![7; 100_000];
+        let stream = zlib_stored(&big);
+        assert_eq!(stream.len(), 2 + 2 * 5 + big.len() + 4);
+        assert_eq!((stream[2], stream[2 + 5 + MAX_STORED]), (0, 1));
+    }
+
+    #[test]
+    fn encode() {
+        let grid = Grid::parse("#.\n.#");
+        let colour = |&b: &u8| if b == b'#' { [255, 0, 0] } else { [0, 0, 0] };
+        let image = Image::from_grid(&grid, 2, colour);
+        assert_eq!((image.width, image.height), (4, 4));
+        assert_eq!(&image.pixels[..12], [255, 0, 0, 255, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+        let mut ppm = Vec::new();
+        image.write_ppm(&mut ppm).unwrap();
+        assert!(ppm.starts_with(b"P6\n4 4\n255\n"));
+        assert_eq!(ppm.len(), 11 + 48);
+
+        let mut png = Vec::new();
+        image.write_png(&mut png).unwrap();
+        assert!(png.starts_with(&PNG_SIGNATURE));
+        assert!(png.ends_with(&[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]));
+        // Signature, IHDR, IDAT with 4 rows of 1 + 12 bytes, IEND.
+        assert_eq!(png.len(), 8 + 25 + 12 + 2 + 5 + 52 + 4 + 12);
+    }
+}
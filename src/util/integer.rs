@@ -1,5 +1,8 @@
 //! Combines common [operators](https://doc.rust-lang.org/book/appendix-02-operators.html)
-//! and constants `0`, `1` and `10` to enable generic methods on integer types.
+//! and constants `0`, `1`, `2` and `10` to enable generic methods on integer types.
+//!
+//! [`Signed`] types are also [`Ord`], so generic code can use `min` and `max` directly, and
+//! provide `abs` and `signum` like the primitive methods of the same name.
 use std::ops::*;
 
 pub trait Integer<T>:
@@ -20,6 +23,7 @@ pub trait Integer<T>:
 {
     const ZERO: T;
     const ONE: T;
+    const TWO: T;
     const TEN: T;
 
     fn trailing_zeros(self) -> u32;
@@ -27,13 +31,19 @@ pub trait Integer<T>:
 
 pub trait Unsigned<T>: Integer<T> {}
 
-pub trait Signed<T>: Integer<T> + Neg<Output = T> {}
+pub trait Signed<T>: Integer<T> + Ord + Neg<Output = T> {
+    const MINUS_ONE: T;
+
+    fn abs(self) -> T;
+    fn signum(self) -> T;
+}
 
 macro_rules! integer {
     ($($t:ty)*) => ($(
         impl Integer<$t> for $t {
             const ZERO: $t = 0;
             const ONE: $t = 1;
+            const TWO: $t = 2;
             const TEN: $t = 10;
 
             #[inline]
@@ -44,6 +54,24 @@ macro_rules! integer {
     )*)
 }
 
+macro_rules! signed {
+    ($($t:ty)*) => ($(
+        impl Signed<$t> for $t {
+            const MINUS_ONE: $t = -1;
+
+            #[inline]
+            fn abs(self) -> $t {
+                <$t>::abs(self)
+            }
+
+            #[inline]
+            fn signum(self) -> $t {
+                <$t>::signum(self)
+            }
+        }
+    )*)
+}
+
 macro_rules! empty_trait {
     ($name:ident for $($t:ty)*) => ($(
         impl $name<$t> for $t {}
@@ -52,4 +80,4 @@ macro_rules! empty_trait {
 
 integer!(u8 u16 u32 u64 u128 usize i16 i32 i64 i128);
 empty_trait!(Unsigned for u8 u16 u32 u64 u128 usize);
-empty_trait!(Signed for i16 i32 i64 i128);
\ No newline at end of file
+signed!(i16 i32 i64 i128);
\ No newline at end of file
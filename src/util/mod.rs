@@ -1,9 +1,25 @@
 #![allow(dead_code)]
 
+pub mod automaton;
+pub mod bitgrid;
+pub mod compress;
+pub mod direction;
+pub mod geometry;
 pub mod grid;
+pub mod gridn;
 pub mod hash;
+pub mod image;
 pub mod heap;
+pub mod hex;
 pub mod point;
+pub mod point3;
+pub mod prefix;
+pub mod rect;
+pub mod render;
+pub mod search;
+pub mod section;
+pub mod sparse;
 pub mod iter;
 pub mod parse;
+pub mod pathfind;
 pub mod integer;
\ No newline at end of file
@@ -11,14 +11,22 @@
 //! reason for the separate methods is that some Advent of Code inputs contain the `-` character
 //! as a delimiter and this would cause numbers to be incorrectly parsed as negative.
 //!
+//! Numbers in other bases, for example hex colours like `#ff8800` or binary strings, are handled
+//! by [`iter_unsigned_radix`] and [`unsigned_radix`]. Any radix from 2 to 36 is supported, with
+//! letters `a-z` (in either case) as digits above 9. A leading `0x`, `0o` or `0b` prefix is skipped
+//! when it matches the requested radix.
+//!
 //! [`iter_unsigned`]: ParseOps::iter_unsigned
 //! [`iter_signed`]: ParseOps::iter_signed
+//! [`iter_unsigned_radix`]: ParseOps::iter_unsigned_radix
+//! [`unsigned_radix`]: ParseOps::unsigned_radix
 use crate::util::integer::*;
 use std::marker::PhantomData;
 use std::str::Bytes;
 
 pub trait ParseByte {
     fn to_decimal(self) -> u8;
+    fn to_digit(self) -> u8;
 }
 
 impl ParseByte for u8 {
@@ -26,6 +34,18 @@ impl ParseByte for u8 {
     fn to_decimal(self) -> u8 {
         self.wrapping_sub(b'0')
     }
+
+    /// Value of the byte as a digit in base 36, or `u8::MAX` if it's not alphanumeric.
+    /// Callers compare the result against their radix.
+    #[inline]
+    fn to_digit(self) -> u8 {
+        match self {
+            b'0'..=b'9' => self - b'0',
+            b'a'..=b'z' => self - b'a' + 10,
+            b'A'..=b'Z' => self - b'A' + 10,
+            _ => u8::MAX,
+        }
+    }
 }
 
 pub struct ParseUnsigned<'a, T> {
@@ -38,11 +58,19 @@ pub struct ParseSigned<'a, T> {
     phantom: PhantomData<T>,
 }
 
+pub struct ParseRadix<'a, T> {
+    bytes: Bytes<'a>,
+    radix: u8,
+    phantom: PhantomData<T>,
+}
+
 pub trait ParseOps {
     fn unsigned<T: Unsigned<T>>(&self) -> T;
     fn signed<T: Signed<T>>(&self) -> T;
+    fn unsigned_radix<T: Unsigned<T>>(&self, radix: u32) -> T;
     fn iter_unsigned<T: Unsigned<T>>(&self) -> ParseUnsigned<'_, T>;
     fn iter_signed<T: Signed<T>>(&self) -> ParseSigned<'_, T>;
+    fn iter_unsigned_radix<T: Unsigned<T>>(&self, radix: u32) -> ParseRadix<'_, T>;
 }
 
 impl<S: AsRef<str>> ParseOps for S {
@@ -56,6 +84,12 @@ impl<S: AsRef<str>> ParseOps for S {
         try_signed(&mut str.bytes()).unwrap_or_else(|| panic!("Unable to parse \"{str}\""))
     }
 
+    fn unsigned_radix<T: Unsigned<T>>(&self, radix: u32) -> T {
+        let str = self.as_ref();
+        try_unsigned_radix(&mut str.bytes(), check_radix(radix))
+            .unwrap_or_else(|| panic!("Unable to parse \"{str}\" in base {radix}"))
+    }
+
     fn iter_unsigned<T: Unsigned<T>>(&self) -> ParseUnsigned<'_, T> {
         ParseUnsigned { bytes: self.as_ref().bytes(), phantom: PhantomData }
     }
@@ -63,6 +97,10 @@ impl<S: AsRef<str>> ParseOps for S {
     fn iter_signed<T: Signed<T>>(&self) -> ParseSigned<'_, T> {
         ParseSigned { bytes: self.as_ref().bytes(), phantom: PhantomData }
     }
+
+    fn iter_unsigned_radix<T: Unsigned<T>>(&self, radix: u32) -> ParseRadix<'_, T> {
+        ParseRadix { bytes: self.as_ref().bytes(), radix: check_radix(radix), phantom: PhantomData }
+    }
 }
 
 impl<T: Unsigned<T>> Iterator for ParseUnsigned<'_, T> {
@@ -95,6 +133,21 @@ impl<T: Signed<T>> Iterator for ParseSigned<'_, T> {
     }
 }
 
+impl<T: Unsigned<T>> Iterator for ParseRadix<'_, T> {
+    type Item = T;
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.bytes.size_hint();
+        (lower / 3, upper.map(|u| u / 3))
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        try_unsigned_radix(&mut self.bytes, self.radix)
+    }
+}
+
 fn try_unsigned<T: Unsigned<T>>(bytes: &mut Bytes<'_>) -> Option<T> {
     let mut n = loop {
         let digit = bytes.next()?.to_decimal();
@@ -134,4 +187,60 @@ fn try_signed<T: Signed<T>>(bytes: &mut Bytes<'_>) -> Option<T> {
     }
 
     Some(if negative { -n } else { n })
-}
\ No newline at end of file
+}
+
+fn try_unsigned_radix<T: Unsigned<T>>(bytes: &mut Bytes<'_>, radix: u8) -> Option<T> {
+    let mut n = loop {
+        let digit = bytes.next()?.to_digit();
+        if digit < radix {
+            break T::from(digit);
+        }
+    };
+
+    // `0x`, `0o` and `0b` are only treated as a prefix directly after a single leading zero.
+    let prefix = match radix {
+        2 => Some(b'b'),
+        8 => Some(b'o'),
+        16 => Some(b'x'),
+        _ => None,
+    };
+    let mut leading_zero = n == T::ZERO;
+    let base = T::from(radix);
+
+    for byte in bytes {
+        let digit = byte.to_digit();
+        if digit >= radix {
+            if leading_zero && prefix == Some(byte.to_ascii_lowercase()) {
+                leading_zero = false;
+                continue;
+            }
+            break;
+        }
+        leading_zero = false;
+        n = base * n + T::from(digit);
+    }
+
+    Some(n)
+}
+
+#[inline]
+fn check_radix(radix: u32) -> u8 {
+    assert!((2..=36).contains(&radix), "Radix must be between 2 and 36, got {radix}");
+    radix as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn radix() {
+        assert_eq!("#ff8800".unsigned_radix::<u32>(16), 0xff8800);
+        let hex: Vec<u32> = "0x1F, 0b101".iter_unsigned_radix(16).collect();
+        assert_eq!(hex, [0x1f, 0xb101]);
+        assert_eq!("0b101 0B11 10".iter_unsigned_radix::<u8>(2).collect::<Vec<_>>(), [5, 3, 2]);
+        assert_eq!("0o17".unsigned_radix::<u128>(8), 15);
+        assert_eq!("zz".unsigned_radix::<u16>(36), 35 * 36 + 35);
+        assert_eq!("0".unsigned_radix::<u64>(16), 0);
+    }
+}
@@ -0,0 +1,370 @@
+//! Weighted shortest paths with [Dijkstra] and [A*], built on [`MinHeap`].
+//!
+//! The graph is implicit: a `neighbors` closure returns the `(node, cost)` pairs reachable from
+//! a node and a `goal` predicate decides when to stop. Besides the cost and one optimal path,
+//! the result keeps every optimal predecessor of each node, so questions like "which tiles are on
+//! *any* shortest path" can be answered with [`ShortestPath::on_any_path`].
+//!
+//! [`astar`] takes a heuristic that must never overestimate the remaining cost and must be
+//! consistent, otherwise the returned cost may not be optimal.
+//!
+//! The `grid_` versions search a [`Grid`] with orthogonal steps, where each cell has a cost to
+//! enter or is a wall. [`grid_dijkstra_facing`] also tracks the direction faced, for puzzles
+//! where turning has a cost, and [`grid_dijkstra_moves`] tracks how far it has moved straight
+//! ahead, for puzzles with momentum where a [`Moves`] rule limits when turns are allowed.
+//!
+//! [Dijkstra]: https://en.wikipedia.org/wiki/Dijkstra%27s_algorithm
+//! [A*]: https://en.wikipedia.org/wiki/A*_search_algorithm
+use crate::util::grid::Grid;
+use crate::util::hash::*;
+use crate::util::heap::MinHeap;
+use crate::util::integer::Integer;
+use crate::util::point::*;
+use std::collections::hash_map::Entry;
+use std::hash::Hash;
+
+pub struct ShortestPath<N, C> {
+    /// Cost of the cheapest path from the start to a goal.
+    pub cost: C,
+    /// Every goal node that can be reached for `cost`.
+    pub goals: Vec<N>,
+    costs: FastMap<N, C>,
+    predecessors: FastMap<N, Vec<N>>,
+}
+
+impl<N: Copy + Eq + Hash, C: Copy> ShortestPath<N, C> {
+    /// Lowest cost found from the start to `node`. Only nodes that were reached before the search
+    /// stopped are known.
+    #[inline]
+    pub fn cost_to(&self, node: N) -> Option<C> {
+        self.costs.get(&node).copied()
+    }
+
+    /// All predecessors of `node` on optimal paths from the start. With zero cost edges this can
+    /// include nodes that are themselves reached through `node`.
+    #[inline]
+    pub fn predecessors(&self, node: N) -> &[N] {
+        self.predecessors.get(&node).map_or(&[], Vec::as_slice)
+    }
+
+    /// One optimal path, from the start up to and including the first goal.
+    pub fn path(&self) -> Vec<N> {
+        let mut path = vec![self.goals[0]];
+        while let Some(&previous) = self.predecessors(*path.last().unwrap()).first() {
+            path.push(previous);
+        }
+        path.reverse();
+        path
+    }
+
+    /// Every node that lies on at least one optimal path to any of the goals.
+    pub fn on_any_path(&self) -> FastSet<N> {
+        let mut seen = FastSet::new();
+        let mut todo = self.goals.clone();
+        seen.extend(todo.iter().copied());
+
+        while let Some(node) = todo.pop() {
+            for &previous in self.predecessors(node) {
+                if seen.insert(previous) {
+                    todo.push(previous);
+                }
+            }
+        }
+
+        seen
+    }
+}
+
+pub fn dijkstra<N, C, I>(
+    start: N,
+    neighbors: impl FnMut(N) -> I,
+    goal: impl FnMut(N) -> bool,
+) -> Option<ShortestPath<N, C>>
+where
+    N: Copy + Eq + Hash,
+    C: Integer<C> + Ord,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, neighbors, goal, |_| C::ZERO)
+}
+
+pub fn astar<N, C, I>(
+    start: N,
+    mut neighbors: impl FnMut(N) -> I,
+    mut goal: impl FnMut(N) -> bool,
+    mut heuristic: impl FnMut(N) -> C,
+) -> Option<ShortestPath<N, C>>
+where
+    N: Copy + Eq + Hash,
+    C: Integer<C> + Ord,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut todo = MinHeap::new();
+    let mut costs = FastMap::new();
+    let mut predecessors: FastMap<N, Vec<N>> = FastMap::new();
+    let mut settled = FastSet::new();
+    let mut best = None;
+    let mut goals = Vec::new();
+
+    todo.push(heuristic(start), start);
+    costs.insert(start, C::ZERO);
+
+    while let Some((estimate, node)) = todo.pop() {
+        // Once the cheapest goal is found, keep going only to collect equally cheap goals.
+        if best.is_some_and(|best| estimate > best) {
+            break;
+        }
+
+        // Skip stale heap entries for nodes that were already reached more cheaply.
+        if !settled.insert(node) {
+            continue;
+        }
+        let cost = costs[&node];
+
+        if goal(node) {
+            if best.is_none() {
+                best = Some(cost);
+            }
+            goals.push(node);
+            continue;
+        }
+
+        for (next, step) in neighbors(node) {
+            let next_cost = cost + step;
+            match costs.entry(next) {
+                Entry::Occupied(mut entry) if next_cost < *entry.get() => {
+                    entry.insert(next_cost);
+                    predecessors.insert(next, vec![node]);
+                }
+                Entry::Occupied(entry) => {
+                    // Settled nodes still collect equally cheap predecessors, as ties with a
+                    // heuristic or zero cost edges can settle a node before all of them are
+                    // expanded. The start never gets any, so following the first predecessor,
+                    // which was always settled earlier, ends there even with zero cost cycles.
+                    if next_cost == *entry.get() && next != start {
+                        predecessors.entry(next).or_default().push(node);
+                    }
+                    continue;
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(next_cost);
+                    predecessors.insert(next, vec![node]);
+                }
+            }
+            todo.push(next_cost + heuristic(next), next);
+        }
+    }
+
+    best.map(|cost| ShortestPath { cost, goals, costs, predecessors })
+}
+
+/// Cheapest path between two cells moving orthogonally. `cost` returns the cost of entering a
+/// cell, or `None` if it's a wall. Uses the Manhattan distance as heuristic, so each step must
+/// cost at least 1.
+pub fn grid_astar<T>(
+    grid: &Grid<T>,
+    start: Point,
+    goal: Point,
+    cost: impl Fn(&T) -> Option<u32>,
+) -> Option<ShortestPath<Point, u32>> {
+    astar(
+        start,
+        |point| grid.neighbors4(point).filter_map(|(next, cell)| Some((next, cost(cell)?))),
+        |point| point == goal,
+        |point| point.manhattan(goal) as u32,
+    )
+}
+
+/// Position on a grid together with the direction it's facing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Facing {
+    pub position: Point,
+    pub direction: Point,
+}
+
+/// Cheapest path to `goal` when each move either steps forward into the next cell, paying
+/// `cost` of that cell, or turns 90 degrees in place, paying `turn`. The goal can be reached
+/// facing any direction, [`ShortestPath::goals`] holds every direction that is optimal.
+pub fn grid_dijkstra_facing<T>(
+    grid: &Grid<T>,
+    start: Facing,
+    goal: Point,
+    cost: impl Fn(&T) -> Option<u32>,
+    turn: u32,
+) -> Option<ShortestPath<Facing, u32>> {
+    dijkstra(
+        start,
+        |Facing { position, direction }| {
+            let forward = position + direction;
+            let step = grid.get(forward).and_then(&cost);
+            let step = step.map(|step| (Facing { position: forward, direction }, step));
+            let left = Facing { position, direction: direction.counter_clockwise() };
+            let right = Facing { position, direction: direction.clockwise() };
+            step.into_iter().chain([(left, turn), (right, turn)])
+        },
+        |facing| facing.position == goal,
+    )
+}
+
+/// Which moves [`grid_dijkstra_moves`] may make from a cell.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Moves {
+    /// Straight steps needed before turning or stopping at the goal.
+    pub min_run: u32,
+    /// Most straight steps in a row before a turn is forced.
+    pub max_run: u32,
+    /// Allowed turns, each combined with a step in the new direction.
+    pub left: bool,
+    pub right: bool,
+    pub reverse: bool,
+    /// Extra cost of each turn, on top of the cost of the cell entered.
+    pub turn: u32,
+}
+
+impl Moves {
+    /// Left and right turns after between `min_run` and `max_run` straight steps.
+    pub fn runs(min_run: u32, max_run: u32) -> Self {
+        Moves { min_run, max_run, ..Moves::default() }
+    }
+}
+
+impl Default for Moves {
+    fn default() -> Self {
+        Moves { min_run: 1, max_run: u32::MAX, left: true, right: true, reverse: false, turn: 0 }
+    }
+}
+
+/// Position on a grid with the direction of the last step and how many steps in a row were
+/// made in that direction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Momentum {
+    pub position: Point,
+    pub direction: Point,
+    pub run: u32,
+}
+
+/// Cheapest path to `goal` where every move steps into the next cell, paying `cost` of that
+/// cell, and the [`Moves`] rule decides when the direction can change. The start has a run of
+/// zero, so it may set off in `start.direction` or any allowed turn from it regardless of
+/// `min_run`. Use [`ShortestPath::path`] for the states along the way.
+pub fn grid_dijkstra_moves<T>(
+    grid: &Grid<T>,
+    start: Facing,
+    goal: Point,
+    moves: &Moves,
+    cost: impl Fn(&T) -> Option<u32>,
+) -> Option<ShortestPath<Momentum, u32>> {
+    let start = Momentum { position: start.position, direction: start.direction, run: 0 };
+    let cost = &cost;
+
+    dijkstra(
+        start,
+        |Momentum { position, direction, run }| {
+            let can_turn = run == 0 || run >= moves.min_run;
+            let turns = [
+                (moves.left, direction.counter_clockwise()),
+                (moves.right, direction.clockwise()),
+                (moves.reverse, direction * -1),
+            ];
+
+            let forward = (run < moves.max_run).then_some((direction, run + 1, 0));
+            let turns = turns
+                .into_iter()
+                .filter(move |&(allowed, _)| allowed && can_turn)
+                .map(|(_, next)| (next, 1, moves.turn));
+
+            forward.into_iter().chain(turns).filter_map(move |(direction, run, extra)| {
+                let position = position + direction;
+                let step = cost(grid.get(position)?)?;
+                Some((Momentum { position, direction, run }, step + extra))
+            })
+        },
+        |state| state.position == goal && state.run >= moves.min_run,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn graph() {
+        // Two equally cheap routes from 0 to 3, through 1 or through 2.
+        let edges = |n: u8| match n {
+            0 => vec![(1, 1_u64), (2, 2), (3, 10)],
+            1 => vec![(3, 3)],
+            2 => vec![(3, 2)],
+            _ => vec![],
+        };
+
+        let result = dijkstra(0, edges, |n| n == 3).unwrap();
+        assert_eq!(result.cost, 4);
+        assert_eq!(result.path().len(), 3);
+        assert_eq!(result.on_any_path(), FastSet::build([0, 1, 2, 3]));
+        assert!(dijkstra(1, edges, |n| n == 0).is_none());
+
+        // Zero cost cycle between 0 and 1, both equally good ways to reach 2.
+        let edges = |n: u8| match n {
+            0 => vec![(1, 0_u64), (2, 1)],
+            1 => vec![(0, 0), (2, 1)],
+            _ => vec![],
+        };
+        let result = dijkstra(0, edges, |n| n == 2).unwrap();
+        assert_eq!(result.path(), [0, 2]);
+        assert_eq!(result.predecessors(2), [0, 1]);
+        assert_eq!(result.on_any_path(), FastSet::build([0, 1, 2]));
+    }
+
+    #[test]
+    fn grid() {
+        let grid = Grid::parse("S.#\n.##\n..E");
+        let cost = |&b: &u8| (b != b'#').then_some(1);
+
+        let result = grid_astar(&grid, ORIGIN, Point::new(2, 2), cost).unwrap();
+        assert_eq!(result.cost, 4);
+        assert_eq!(result.path().first(), Some(&ORIGIN));
+        assert_eq!(result.on_any_path().len(), 5);
+
+        // Every cell of an open grid is on some shortest path between opposite corners, and
+        // with the heuristic many of them tie.
+        for size in 4..8 {
+            let open = Grid::new(size, size, b'.');
+            let goal = Point::new(size - 1, size - 1);
+            let result = grid_astar(&open, ORIGIN, goal, cost).unwrap();
+            assert_eq!(result.cost, 2 * (size as u32 - 1));
+            assert_eq!(result.on_any_path().len(), (size * size) as usize);
+        }
+
+        let start = Facing { position: ORIGIN, direction: RIGHT };
+        let result = grid_dijkstra_facing(&grid, start, Point::new(2, 2), cost, 1000).unwrap();
+        assert_eq!(result.cost, 2004);
+        assert_eq!(result.goals, [Facing { position: Point::new(2, 2), direction: RIGHT }]);
+    }
+
+    #[test]
+    fn momentum() {
+        let grid = Grid::parse(
+            "2413432311323\n3215453535623\n3255245654254\n3446585845452\n4546657867536\n\
+             1438598798454\n4457876987766\n3637877979653\n4654967986887\n4564679986453\n\
+             1224686865563\n2546548887735\n4322674655533",
+        );
+        let cost = |&b: &u8| Some((b - b'0') as u32);
+        let start = Facing { position: ORIGIN, direction: RIGHT };
+        let goal = Point::new(grid.width - 1, grid.height - 1);
+
+        let result = grid_dijkstra_moves(&grid, start, goal, &Moves::runs(1, 3), cost).unwrap();
+        assert_eq!(result.cost, 102);
+        let path = result.path();
+        assert_eq!((path[0].position, path.last().unwrap().position), (ORIGIN, goal));
+        assert!(path.iter().all(|state| state.run <= 3));
+
+        let result = grid_dijkstra_moves(&grid, start, goal, &Moves::runs(4, 10), cost).unwrap();
+        assert_eq!(result.cost, 94);
+
+        let grid = "111111111111\n999999999991\n999999999991\n999999999991\n999999999991";
+        let grid = Grid::parse(grid);
+        let goal = Point::new(grid.width - 1, grid.height - 1);
+        let result = grid_dijkstra_moves(&grid, start, goal, &Moves::runs(4, 10), cost).unwrap();
+        assert_eq!(result.cost, 71);
+    }
+}
@@ -1,3 +1,10 @@
+//! Two dimensional point, generic over [`Signed`] coordinates.
+//!
+//! `Point` on its own means `Point<i32>`, which is what grids use and what the direction
+//! constants below are. Puzzles whose coordinates overflow `i32` can use `Point<i64>` or
+//! `Point<i128>` with the same operations, and the matching associated constants such as
+//! `Point::<i64>::UP`.
+use crate::util::integer::*;
 use std::hash::{Hash, Hasher};
 use std::ops::{Add, AddAssign, Mul, Sub, SubAssign};
 
@@ -20,17 +27,25 @@ pub const DIAGONAL: [Point; 8] = [
 ];
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
-pub struct Point {
-    pub x: i32,
-    pub y: i32,
+pub struct Point<T = i32> {
+    pub x: T,
+    pub y: T,
 }
 
-impl Point {
+impl<T> Point<T> {
     #[inline]
     #[must_use]
-    pub const fn new(x: i32, y: i32) -> Self {
+    pub const fn new(x: T, y: T) -> Self {
         Point { x, y }
     }
+}
+
+impl<T: Signed<T>> Point<T> {
+    pub const ORIGIN: Self = Point::new(T::ZERO, T::ZERO);
+    pub const UP: Self = Point::new(T::ZERO, T::MINUS_ONE);
+    pub const DOWN: Self = Point::new(T::ZERO, T::ONE);
+    pub const LEFT: Self = Point::new(T::MINUS_ONE, T::ZERO);
+    pub const RIGHT: Self = Point::new(T::ONE, T::ZERO);
 
     #[inline]
     #[must_use]
@@ -46,7 +61,7 @@ impl Point {
 
     #[inline]
     #[must_use]
-    pub fn manhattan(self, other: Self) -> i32 {
+    pub fn manhattan(self, other: Self) -> T {
         (self.x - other.x).abs() + (self.y - other.y).abs()
     }
 
@@ -57,28 +72,32 @@ impl Point {
     }
 }
 
-impl From<u8> for Point {
+/// Unit step for an arrow `^v<>` or letter `UDLR`, panicking on anything else. Use
+/// [`Direction::try_from`] for compass points or when the input may contain other bytes.
+///
+/// [`Direction::try_from`]: crate::util::direction::Direction
+impl<T: Signed<T>> From<u8> for Point<T> {
     #[inline]
     fn from(value: u8) -> Self {
         match value {
-            b'^' | b'U' => UP,
-            b'v' | b'D' => DOWN,
-            b'<' | b'L' => LEFT,
-            b'>' | b'R' => RIGHT,
+            b'^' | b'U' => Point::UP,
+            b'v' | b'D' => Point::DOWN,
+            b'<' | b'L' => Point::LEFT,
+            b'>' | b'R' => Point::RIGHT,
             _ => unreachable!(),
         }
     }
 }
 
-impl Hash for Point {
+impl<T: Hash> Hash for Point<T> {
     #[inline]
     fn hash<H: Hasher>(&self, state: &mut H) {
-        state.write_u32(self.x as u32);
-        state.write_u32(self.y as u32);
+        self.x.hash(state);
+        self.y.hash(state);
     }
 }
 
-impl Add for Point {
+impl<T: Signed<T>> Add for Point<T> {
     type Output = Self;
 
     #[inline]
@@ -87,24 +106,23 @@ impl Add for Point {
     }
 }
 
-impl AddAssign for Point {
+impl<T: Signed<T>> AddAssign for Point<T> {
     #[inline]
     fn add_assign(&mut self, rhs: Self) {
-        self.x += rhs.x;
-        self.y += rhs.y;
+        *self = *self + rhs;
     }
 }
 
-impl Mul<i32> for Point {
+impl<T: Signed<T>> Mul<T> for Point<T> {
     type Output = Self;
 
     #[inline]
-    fn mul(self, rhs: i32) -> Self {
+    fn mul(self, rhs: T) -> Self {
         Point::new(self.x * rhs, self.y * rhs)
     }
 }
 
-impl Sub for Point {
+impl<T: Signed<T>> Sub for Point<T> {
     type Output = Self;
 
     #[inline]
@@ -113,10 +131,24 @@ impl Sub for Point {
     }
 }
 
-impl SubAssign for Point {
+impl<T: Signed<T>> SubAssign for Point<T> {
     #[inline]
     fn sub_assign(&mut self, rhs: Self) {
-        self.x -= rhs.x;
-        self.y -= rhs.y;
+        *self = *self - rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generic() {
+        let far = Point::new(3_000_000_000_i64, -4_000_000_000);
+        assert_eq!(far.manhattan(Point::ORIGIN), 7_000_000_000);
+        assert_eq!(far.signum(Point::ORIGIN), Point::new(1, -1));
+        assert_eq!(Point::<i64>::from(b'^').clockwise(), Point::RIGHT);
+        assert_eq!(far + Point::DOWN * 2, Point::new(3_000_000_000, -3_999_999_998));
+        assert_eq!(UP.counter_clockwise(), LEFT);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,244 @@
+//! Three dimensional point or vector, generic over [`Signed`] coordinates like [`Point`].
+//!
+//! Rotations are quarter turns following the right hand rule, so [`Point3::rotate_z`] turns the
+//! x axis into the y axis. [`Point3::orientations`] applies all 24 rotations of a cube, which is
+//! what puzzles with scanners or dice in unknown orientations need.
+//!
+//! Points can be parsed straight from the input:
+//!
+//! ```none
+//! let points: Vec<Point3<i64>> = input.iter_signed().chunk::<3>().map(Point3::from).collect();
+//! ```
+//!
+//! [`Point`]: crate::util::point::Point
+use crate::util::integer::*;
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+pub const ORIGIN3: Point3 = Point3::new(0, 0, 0);
+/// Neighbors differing by one along a single axis.
+pub const NEIGHBORS6: [Point3; 6] = [
+    Point3::new(-1, 0, 0),
+    Point3::new(1, 0, 0),
+    Point3::new(0, -1, 0),
+    Point3::new(0, 1, 0),
+    Point3::new(0, 0, -1),
+    Point3::new(0, 0, 1),
+];
+/// Neighbors differing by at most one along every axis, ordered by z, then y, then x.
+pub const NEIGHBORS26: [Point3; 26] = {
+    let mut neighbors = [ORIGIN3; 26];
+    let mut i = 0;
+    let mut n = 0;
+    while n < 27 {
+        // Skip the center, which is the 14th of the 27 cells.
+        if n != 13 {
+            let (x, y, z) = (n % 3 - 1, n / 3 % 3 - 1, n / 9 - 1);
+            neighbors[i] = Point3::new(x, y, z);
+            i += 1;
+        }
+        n += 1;
+    }
+    neighbors
+};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Point3<T = i32> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point3<T> {
+    #[inline]
+    #[must_use]
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T: Signed<T>> Point3<T> {
+    #[inline]
+    #[must_use]
+    pub fn manhattan(self, other: Self) -> T {
+        let d = self - other;
+        d.x.abs() + d.y.abs() + d.z.abs()
+    }
+
+    /// Largest difference along any axis, the number of king moves between the points.
+    #[inline]
+    #[must_use]
+    pub fn chebyshev(self, other: Self) -> T {
+        let d = self - other;
+        d.x.abs().max(d.y.abs()).max(d.z.abs())
+    }
+
+    /// Square of the straight line distance, exact unlike the distance itself.
+    #[inline]
+    #[must_use]
+    pub fn euclidean_squared(self, other: Self) -> T {
+        let d = self - other;
+        d.dot(d)
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn dot(self, other: Self) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn cross(self, other: Self) -> Self {
+        Point3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    /// Quarter turn around the x axis, taking y to z.
+    #[inline]
+    #[must_use]
+    pub fn rotate_x(self) -> Self {
+        Point3::new(self.x, -self.z, self.y)
+    }
+
+    /// Quarter turn around the y axis, taking z to x.
+    #[inline]
+    #[must_use]
+    pub fn rotate_y(self) -> Self {
+        Point3::new(self.z, self.y, -self.x)
+    }
+
+    /// Quarter turn around the z axis, taking x to y.
+    #[inline]
+    #[must_use]
+    pub fn rotate_z(self) -> Self {
+        Point3::new(-self.y, self.x, self.z)
+    }
+
+    /// The point under each of the 24 rotations of a cube, starting with the identity. The same
+    /// index always means the same rotation, so it can be used to rotate several points alike.
+    pub fn orientations(self) -> [Self; 24] {
+        // Turn the x axis to face each of the 6 directions, after spinning around it 4 times.
+        let faces: [fn(Self) -> Self; 6] = [
+            |p| p,
+            Point3::rotate_y,
+            |p| p.rotate_y().rotate_y(),
+            |p| p.rotate_y().rotate_y().rotate_y(),
+            Point3::rotate_z,
+            |p| p.rotate_z().rotate_z().rotate_z(),
+        ];
+
+        let mut orientations = [self; 24];
+        let mut spun = self;
+        for spin in 0..4 {
+            for (face, turn) in faces.iter().enumerate() {
+                orientations[face * 4 + spin] = turn(spun);
+            }
+            spun = spun.rotate_x();
+        }
+        orientations
+    }
+}
+
+impl<T> From<[T; 3]> for Point3<T> {
+    #[inline]
+    fn from([x, y, z]: [T; 3]) -> Self {
+        Point3::new(x, y, z)
+    }
+}
+
+impl<T: Hash> Hash for Point3<T> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.x.hash(state);
+        self.y.hash(state);
+        self.z.hash(state);
+    }
+}
+
+impl<T: Signed<T>> Add for Point3<T> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Point3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T: Signed<T>> AddAssign for Point3<T> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Signed<T>> Mul<T> for Point3<T> {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: T) -> Self {
+        Point3::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl<T: Signed<T>> Neg for Point3<T> {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Point3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T: Signed<T>> Sub for Point3<T> {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Point3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T: Signed<T>> SubAssign for Point3<T> {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::hash::*;
+    use crate::util::iter::ChunkOps as _;
+    use crate::util::parse::ParseOps as _;
+
+    #[test]
+    fn point3() {
+        let points: Vec<Point3<i64>> =
+            "1,-2,3\n-4,5,6".iter_signed().chunk::<3>().map(Point3::from).collect();
+        let (a, b) = (points[0], points[1]);
+
+        assert_eq!(a - b, Point3::new(5, -7, -3));
+        assert_eq!(a.manhattan(b), 15);
+        assert_eq!(a.chebyshev(b), 7);
+        assert_eq!(a.euclidean_squared(b), 83);
+        assert_eq!(a.dot(b), 4);
+        assert_eq!(a.cross(b), Point3::new(-27, -18, -3));
+
+        let x = Point3::new(1, 0, 0);
+        assert_eq!(x.rotate_z(), Point3::new(0, 1, 0));
+        assert_eq!(x.rotate_z().rotate_x(), Point3::new(0, 0, 1));
+        assert_eq!(x.rotate_z().rotate_x().rotate_y(), x);
+
+        let orientations = Point3::new(1, 2, 3).orientations();
+        assert_eq!(orientations[0], Point3::new(1, 2, 3));
+        assert_eq!(orientations.iter().collect::<FastSet<_>>().len(), 24);
+
+        assert!(NEIGHBORS26.iter().all(|&n| n.chebyshev(ORIGIN3) == 1));
+        assert_eq!(NEIGHBORS26.iter().filter(|&&n| n.manhattan(ORIGIN3) == 1).count(), 6);
+    }
+}
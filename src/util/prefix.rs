@@ -0,0 +1,169 @@
+//! Two dimensional [prefix sums] for constant time rectangle queries, and the matching
+//! difference array for constant time rectangle updates.
+//!
+//! A [`SummedAreaTable`] answers "what is the sum of all cells in this rectangle" in O(1) after an
+//! O(n) build. A [`DifferenceGrid`] is the inverse: many "add `v` to every cell in this
+//! rectangle" updates in O(1) each, followed by one O(n) pass to recover the cell values.
+//!
+//! Rectangles are given by their top left and bottom right corners, both inclusive. Both types
+//! store an extra row and column of zeros internally so callers never need sentinel values.
+//!
+//! [prefix sums]: https://en.wikipedia.org/wiki/Summed-area_table
+use crate::util::bitgrid::BitGrid;
+use crate::util::grid::Grid;
+use crate::util::integer::*;
+use crate::util::point::*;
+
+pub struct SummedAreaTable<T> {
+    pub width: i32,
+    pub height: i32,
+    sums: Vec<T>,
+}
+
+impl<T: Integer<T>> SummedAreaTable<T> {
+    pub fn new(grid: &Grid<T>) -> Self {
+        SummedAreaTable::from_fn(grid, |&value| value)
+    }
+
+    /// Counts the cells of `grid` for which `predicate` holds.
+    pub fn from_predicate<U>(grid: &Grid<U>, predicate: impl Fn(&U) -> bool) -> Self {
+        SummedAreaTable::from_fn(grid, |cell| if predicate(cell) { T::ONE } else { T::ZERO })
+    }
+
+    /// Sums the value of `f` for each cell of `grid`.
+    pub fn from_fn<U>(grid: &Grid<U>, f: impl Fn(&U) -> T) -> Self {
+        SummedAreaTable::build(grid.width, grid.height, |p| f(&grid[p]))
+    }
+
+    /// Counts the set cells of `bits`.
+    pub fn from_bits(bits: &BitGrid) -> Self {
+        SummedAreaTable::build(bits.width, bits.height, |p| if bits[p] { T::ONE } else { T::ZERO })
+    }
+
+    fn build(width: i32, height: i32, f: impl Fn(Point) -> T) -> Self {
+        let stride = (width + 1) as usize;
+        let mut sums = vec![T::ZERO; stride * (height + 1) as usize];
+
+        for y in 0..height {
+            let mut row = T::ZERO;
+            for x in 0..width {
+                row = row + f(Point::new(x, y));
+                let index = (y as usize + 1) * stride + x as usize + 1;
+                sums[index] = sums[index - stride] + row;
+            }
+        }
+
+        SummedAreaTable { width, height, sums }
+    }
+
+    /// Sum of all cells from `min` to `max` inclusive. Empty rectangles sum to zero.
+    #[inline]
+    pub fn sum(&self, min: Point, max: Point) -> T {
+        if min.x > max.x || min.y > max.y {
+            return T::ZERO;
+        }
+        assert!(
+            min.x >= 0 && min.y >= 0 && max.x < self.width && max.y < self.height,
+            "Rectangle {min:?} to {max:?} out of bounds"
+        );
+
+        // Added first and subtracted after so unsigned types can't underflow.
+        let inside = self.at(max.x + 1, max.y + 1) + self.at(min.x, min.y);
+        inside - self.at(min.x, max.y + 1) - self.at(max.x + 1, min.y)
+    }
+
+    #[inline]
+    pub fn total(&self) -> T {
+        self.at(self.width, self.height)
+    }
+
+    /// Sum of all cells above and to the left of `(x, y)`, exclusive.
+    #[inline]
+    fn at(&self, x: i32, y: i32) -> T {
+        self.sums[(y * (self.width + 1) + x) as usize]
+    }
+}
+
+pub struct DifferenceGrid<T> {
+    pub width: i32,
+    pub height: i32,
+    deltas: Vec<T>,
+}
+
+impl<T: Signed<T>> DifferenceGrid<T> {
+    pub fn new(width: i32, height: i32) -> Self {
+        let deltas = vec![T::ZERO; ((width + 1) * (height + 1)) as usize];
+        DifferenceGrid { width, height, deltas }
+    }
+
+    /// Adds `value` to every cell from `min` to `max` inclusive.
+    #[inline]
+    pub fn add(&mut self, min: Point, max: Point, value: T) {
+        if min.x > max.x || min.y > max.y {
+            return;
+        }
+        assert!(
+            min.x >= 0 && min.y >= 0 && max.x < self.width && max.y < self.height,
+            "Rectangle {min:?} to {max:?} out of bounds"
+        );
+
+        self.update(min.x, min.y, value);
+        self.update(max.x + 1, min.y, -value);
+        self.update(min.x, max.y + 1, -value);
+        self.update(max.x + 1, max.y + 1, value);
+    }
+
+    /// Value of every cell after all updates.
+    pub fn build(&self) -> Grid<T> {
+        let stride = (self.width + 1) as usize;
+        let mut grid = Grid::new(self.width, self.height, T::ZERO);
+        let mut above = vec![T::ZERO; stride];
+
+        for y in 0..self.height {
+            let mut row = T::ZERO;
+            for x in 0..self.width {
+                row = row + self.deltas[y as usize * stride + x as usize];
+                above[x as usize] = above[x as usize] + row;
+                grid[Point::new(x, y)] = above[x as usize];
+            }
+        }
+
+        grid
+    }
+
+    #[inline]
+    fn update(&mut self, x: i32, y: i32, value: T) {
+        let index = (y * (self.width + 1) + x) as usize;
+        self.deltas[index] = self.deltas[index] + value;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summed_area() {
+        let grid = Grid { width: 3, height: 2, bytes: vec![1_u32, 2, 3, 4, 5, 6] };
+        let table = SummedAreaTable::new(&grid);
+
+        assert_eq!(table.total(), 21);
+        assert_eq!(table.sum(ORIGIN, ORIGIN), 1);
+        assert_eq!(table.sum(Point::new(1, 0), Point::new(2, 1)), 16);
+        assert_eq!(table.sum(Point::new(0, 1), Point::new(2, 1)), 15);
+        assert_eq!(table.sum(Point::new(2, 0), Point::new(1, 1)), 0);
+
+        let walls = SummedAreaTable::<u8>::from_predicate(&Grid::parse("#.#\n##."), |&b| b == b'#');
+        assert_eq!(walls.sum(ORIGIN, Point::new(1, 1)), 3);
+    }
+
+    #[test]
+    fn difference() {
+        let mut diff = DifferenceGrid::new(3, 3);
+        diff.add(ORIGIN, Point::new(1, 1), 2_i32);
+        diff.add(Point::new(1, 1), Point::new(2, 2), -1);
+        diff.add(Point::new(2, 0), Point::new(2, 0), 5);
+
+        assert_eq!(diff.build().bytes, [2, 2, 5, 2, 1, -1, 0, -1, -1]);
+    }
+}
@@ -0,0 +1,195 @@
+//! Axis aligned rectangles of integer cells.
+//!
+//! A [`Rect`] is stored as its top left and bottom right corners, both inclusive, the same
+//! convention as [`SummedAreaTable::sum`] and [`CompressedGrid::fill_rect`]. A rectangle whose
+//! `max` is less than its `min` along either axis is empty, so operations like
+//! [`Rect::intersection`] never need a special case for it.
+//!
+//! [`BoundingBox`] is the same type, for the common case of finding the smallest rectangle that
+//! contains a set of points in order to size a [`Grid`]. [`SparseGrid::bounds`] returns one too:
+//!
+//! ```none
+//! let bounds = BoundingBox::from_points(points).unwrap();
+//! let grid = Grid::new(bounds.width(), bounds.height(), b'.');
+//! ```
+//!
+//! [`SummedAreaTable::sum`]: crate::util::prefix::SummedAreaTable::sum
+//! [`CompressedGrid::fill_rect`]: crate::util::compress::CompressedGrid::fill_rect
+//! [`Grid`]: crate::util::grid::Grid
+//! [`SparseGrid::bounds`]: crate::util::sparse::SparseGrid::bounds
+use crate::util::integer::*;
+use crate::util::point::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rect<T = i32> {
+    pub min: Point<T>,
+    pub max: Point<T>,
+}
+
+/// Smallest rectangle containing a set of points.
+pub type BoundingBox<T = i32> = Rect<T>;
+
+impl<T: Signed<T>> Rect<T> {
+    /// Rectangle from `min` to `max`, both inclusive.
+    #[inline]
+    pub const fn new(min: Point<T>, max: Point<T>) -> Self {
+        Rect { min, max }
+    }
+
+    /// Rectangle from `min` inclusive to `end` exclusive.
+    #[inline]
+    pub fn exclusive(min: Point<T>, end: Point<T>) -> Self {
+        Rect { min, max: Point::new(end.x - T::ONE, end.y - T::ONE) }
+    }
+
+    /// Rectangle with `a` and `b` as opposite corners, in any order.
+    #[inline]
+    pub fn from_corners(a: Point<T>, b: Point<T>) -> Self {
+        let min = Point::new(a.x.min(b.x), a.y.min(b.y));
+        let max = Point::new(a.x.max(b.x), a.y.max(b.y));
+        Rect { min, max }
+    }
+
+    /// Smallest rectangle containing every point, or `None` if there are none.
+    pub fn from_points(points: impl IntoIterator<Item = Point<T>>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Rect::new(first, first), |rect, p| rect.union(Rect::new(p, p))))
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.max.x < self.min.x || self.max.y < self.min.y
+    }
+
+    #[inline]
+    pub fn width(&self) -> T {
+        if self.is_empty() { T::ZERO } else { self.max.x - self.min.x + T::ONE }
+    }
+
+    #[inline]
+    pub fn height(&self) -> T {
+        if self.is_empty() { T::ZERO } else { self.max.y - self.min.y + T::ONE }
+    }
+
+    /// Number of cells covered.
+    #[inline]
+    pub fn area(&self) -> T {
+        self.width() * self.height()
+    }
+
+    #[inline]
+    pub fn contains(&self, p: Point<T>) -> bool {
+        self.min.x <= p.x && p.x <= self.max.x && self.min.y <= p.y && p.y <= self.max.y
+    }
+
+    /// Whether every cell of `other` is also in this rectangle. Empty rectangles are contained
+    /// in everything.
+    #[inline]
+    pub fn contains_rect(&self, other: &Self) -> bool {
+        other.is_empty() || (self.contains(other.min) && self.contains(other.max))
+    }
+
+    /// Cells in both rectangles, or `None` if they don't overlap.
+    #[inline]
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let rect = Rect {
+            min: Point::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y)),
+            max: Point::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y)),
+        };
+        (!rect.is_empty()).then_some(rect)
+    }
+
+    /// Smallest rectangle containing both rectangles. Empty rectangles are ignored.
+    #[inline]
+    pub fn union(self, other: Self) -> Self {
+        if self.is_empty() {
+            return other;
+        }
+        if other.is_empty() {
+            return self;
+        }
+        let min = Point::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y));
+        let max = Point::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y));
+        Rect { min, max }
+    }
+
+    /// Splits into the columns left of `x` and the columns from `x` onwards. Either half may be
+    /// empty.
+    #[inline]
+    pub fn split_x(&self, x: T) -> (Self, Self) {
+        let left = Rect::new(self.min, Point::new(self.max.x.min(x - T::ONE), self.max.y));
+        let right = Rect::new(Point::new(self.min.x.max(x), self.min.y), self.max);
+        (left, right)
+    }
+
+    /// Splits into the rows above `y` and the rows from `y` onwards. Either half may be empty.
+    #[inline]
+    pub fn split_y(&self, y: T) -> (Self, Self) {
+        let top = Rect::new(self.min, Point::new(self.max.x, self.max.y.min(y - T::ONE)));
+        let bottom = Rect::new(Point::new(self.min.x, self.min.y.max(y)), self.max);
+        (top, bottom)
+    }
+
+    /// Every cell, left to right and top to bottom.
+    #[inline]
+    pub fn points(&self) -> Points<T> {
+        let next = (!self.is_empty()).then_some(self.min);
+        Points { rect: *self, next }
+    }
+}
+
+pub struct Points<T> {
+    rect: Rect<T>,
+    next: Option<Point<T>>,
+}
+
+impl<T: Signed<T>> Iterator for Points<T> {
+    type Item = Point<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Point<T>> {
+        let p = self.next?;
+        self.next = if p.x < self.rect.max.x {
+            Some(Point::new(p.x + T::ONE, p.y))
+        } else if p.y < self.rect.max.y {
+            Some(Point::new(self.rect.min.x, p.y + T::ONE))
+        } else {
+            None
+        };
+        Some(p)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rect() {
+        let a = Rect::from_corners(Point::new(3, 0), ORIGIN);
+        let b = Rect::exclusive(Point::new(2, -1), Point::new(5, 2));
+        assert_eq!(a, Rect::new(ORIGIN, Point::new(3, 0)));
+        assert_eq!((b.width(), b.height(), b.area()), (3, 3, 9));
+        assert!(b.contains(Point::new(4, 1)) && !b.contains(Point::new(5, 1)));
+
+        assert_eq!(a.intersection(&b), Some(Rect::new(Point::new(2, 0), Point::new(3, 0))));
+        assert_eq!(a.union(b), Rect::new(Point::new(0, -1), Point::new(4, 1)));
+        assert!(a.union(b).contains_rect(&a));
+        assert_eq!(a.intersection(&Rect::new(Point::new(9, 9), Point::new(9, 9))), None);
+
+        let (left, right) = a.split_x(1);
+        assert_eq!((left.area(), right.area()), (1, 3));
+        let (top, bottom) = a.split_y(0);
+        assert!(top.is_empty() && top.points().next().is_none());
+        assert_eq!(bottom, a);
+
+        let points: Vec<_> = b.points().collect();
+        assert_eq!(points.len(), 9);
+        assert_eq!((points[1], points[3]), (Point::new(3, -1), Point::new(2, 0)));
+        assert_eq!(BoundingBox::from_points(points), Some(b));
+
+        let big = Rect::from_corners(Point::new(7_i64, 1), Point::new(98_765_432_100, 3));
+        assert_eq!(big.area(), 98_765_432_094 * 3);
+    }
+}
@@ -0,0 +1,215 @@
+//! Text rendering of grids, with optional overlays for debugging.
+//!
+//! Any grid whose cells implement [`Glyph`] can be formatted with `{}`. For anything more, a
+//! [`Renderer`] draws paths, points and labels on top of the grid, either in plain text or with
+//! [ANSI colours], and writes the result to any [`io::Write`]:
+//!
+//! ```none
+//! Renderer::new(&grid)
+//!     .path(&path, Colour::Green)
+//!     .point(start, 'S', Colour::Red)
+//!     .label(Point::new(0, 0), "step 3", Colour::Yellow)
+//!     .ansi(true)
+//!     .render(&mut io::stdout())?;
+//! ```
+//!
+//! Later overlays are drawn over earlier ones. Overlays outside the grid are ignored.
+//!
+//! [ANSI colours]: https://en.wikipedia.org/wiki/ANSI_escape_code#Colors
+use crate::util::grid::Grid;
+use crate::util::hash::*;
+use crate::util::point::*;
+use std::io;
+
+/// Maps a cell to the character used to display it.
+pub trait Glyph {
+    fn glyph(&self) -> char;
+}
+
+impl Glyph for u8 {
+    #[inline]
+    fn glyph(&self) -> char {
+        *self as char
+    }
+}
+
+impl Glyph for char {
+    #[inline]
+    fn glyph(&self) -> char {
+        *self
+    }
+}
+
+impl Glyph for bool {
+    #[inline]
+    fn glyph(&self) -> char {
+        if *self { '#' } else { '.' }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Colour {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Colour {
+    #[inline]
+    fn code(self) -> u8 {
+        match self {
+            Colour::Red => 31,
+            Colour::Green => 32,
+            Colour::Yellow => 33,
+            Colour::Blue => 34,
+            Colour::Magenta => 35,
+            Colour::Cyan => 36,
+        }
+    }
+}
+
+/// Character drawn over a cell, keeping the cell's own glyph when `glyph` is `None`.
+#[derive(Clone, Copy)]
+struct Mark {
+    glyph: Option<char>,
+    colour: Colour,
+}
+
+pub struct Renderer<'a, T> {
+    grid: &'a Grid<T>,
+    marks: FastMap<Point, Mark>,
+    ansi: bool,
+}
+
+impl<'a, T: Glyph> Renderer<'a, T> {
+    pub fn new(grid: &'a Grid<T>) -> Self {
+        Renderer { grid, marks: FastMap::new(), ansi: false }
+    }
+
+    /// Whether to colour overlays with ANSI escape codes. Defaults to plain text.
+    pub fn ansi(mut self, ansi: bool) -> Self {
+        self.ansi = ansi;
+        self
+    }
+
+    /// Draws `glyph` at `p`.
+    pub fn point(mut self, p: Point, glyph: char, colour: Colour) -> Self {
+        self.mark(p, Some(glyph), colour);
+        self
+    }
+
+    /// Colours the cells at `points`, keeping their glyph.
+    pub fn highlight(mut self, points: impl IntoIterator<Item = Point>, colour: Colour) -> Self {
+        for p in points {
+            self.mark(p, None, colour);
+        }
+        self
+    }
+
+    /// Draws arrows along a path through `points`. Consecutive points may be more than one
+    /// cell apart if they are on the same row, column or diagonal, in which case every cell in
+    /// between is drawn too. The last point is drawn as `o`.
+    pub fn path(mut self, points: &[Point], colour: Colour) -> Self {
+        for pair in points.windows(2) {
+            let (from, to) = (pair[0], pair[1]);
+            let step = to.signum(from);
+            let delta = to - from;
+
+            if delta.x == 0 || delta.y == 0 || delta.x.abs() == delta.y.abs() {
+                let mut p = from;
+                while p != to {
+                    self.mark(p, Some(arrow(step)), colour);
+                    p += step;
+                }
+            } else {
+                self.mark(from, Some(arrow(step)), colour);
+            }
+        }
+        if let Some(&last) = points.last() {
+            self.mark(last, Some('o'), colour);
+        }
+        self
+    }
+
+    /// Writes `text` from `p` to the right, cut off at the edge of the grid.
+    pub fn label(mut self, p: Point, text: &str, colour: Colour) -> Self {
+        for (i, c) in text.chars().enumerate() {
+            self.mark(p + RIGHT * i as i32, Some(c), colour);
+        }
+        self
+    }
+
+    pub fn render(&self, out: &mut impl io::Write) -> io::Result<()> {
+        for y in 0..self.grid.height {
+            let mut line = String::with_capacity(self.grid.width as usize);
+            for x in 0..self.grid.width {
+                let p = Point::new(x, y);
+                let glyph = self.grid[p].glyph();
+
+                match self.marks.get(&p) {
+                    Some(mark) if self.ansi => {
+                        let glyph = mark.glyph.unwrap_or(glyph);
+                        line.push_str(&format!("\x1b[{}m{glyph}\x1b[0m", mark.colour.code()));
+                    }
+                    Some(mark) => line.push(mark.glyph.unwrap_or(glyph)),
+                    None => line.push(glyph),
+                }
+            }
+            writeln!(out, "{line}")?;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn mark(&mut self, p: Point, glyph: Option<char>, colour: Colour) {
+        if self.grid.contains(p) {
+            self.marks.insert(p, Mark { glyph, colour });
+        }
+    }
+}
+
+#[inline]
+fn arrow(step: Point) -> char {
+    match (step.x, step.y) {
+        (0, -1) => '^',
+        (0, 1) => 'v',
+        (-1, 0) => '<',
+        (1, 0) => '>',
+        (1, -1) | (-1, 1) => '/',
+        (1, 1) | (-1, -1) => '\\',
+        _ => '*',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::sparse::SparseGrid;
+
+    #[test]
+    fn render() {
+        let grid = Grid::parse("....\n.#..\n....");
+        assert_eq!(grid.to_string(), "....\n.#..\n....");
+        assert_eq!(Grid::new(0, 2, b'.').to_string(), "\n");
+        assert_eq!(SparseGrid::<u8>::new().to_grid(b'.').to_string(), "");
+        let mut empty = Vec::new();
+        Renderer::new(&Grid::new(0, 0, b'.')).render(&mut empty).unwrap();
+        assert!(empty.is_empty());
+
+        let renderer = Renderer::new(&grid)
+            .path(&[ORIGIN, Point::new(3, 0), Point::new(3, 2)], Colour::Green)
+            .point(Point::new(1, 1), 'S', Colour::Red)
+            .label(Point::new(0, 2), "hello", Colour::Yellow);
+
+        let mut plain = Vec::new();
+        renderer.render(&mut plain).unwrap();
+        assert_eq!(String::from_utf8(plain).unwrap(), ">>>v\n.S.v\nhell\n");
+
+        let mut ansi = Vec::new();
+        Renderer::new(&grid).highlight([ORIGIN], Colour::Blue).ansi(true).render(&mut ansi).unwrap();
+        assert!(String::from_utf8(ansi).unwrap().starts_with("\x1b[34m.\x1b[0m..."));
+    }
+}
@@ -0,0 +1,288 @@
+//! Unweighted graph searches: [BFS] distance maps, flood fill and [connected components].
+//!
+//! Each search comes in two flavours. The generic version works on any implicit graph where
+//! nodes are `Copy + Eq + Hash` and edges are given by a `neighbors` closure, for example:
+//!
+//! ```none
+//!   let open = |p| grid.neighbors4(p).filter(|(_, &c)| c != b'#').map(|(n, _)| n);
+//!   let distances = bfs([start], open);
+//! ```
+//!
+//! The `grid_` versions move orthogonally between cells of a [`Grid`] and store their results in
+//! a grid of the same size, which is much faster than hashing every [`Point`]. Whether a move is
+//! allowed is decided by a predicate on the cells moved from and to.
+//!
+//! All searches accept several start nodes, so multi-source BFS is the same call with more
+//! starts.
+//!
+//! [BFS]: https://en.wikipedia.org/wiki/Breadth-first_search
+//! [connected components]: https://en.wikipedia.org/wiki/Component_(graph_theory)
+use crate::util::grid::Grid;
+use crate::util::hash::*;
+use crate::util::point::*;
+use std::collections::VecDeque;
+use std::collections::hash_map::Entry;
+use std::hash::Hash;
+
+/// Distance of cells that can't be reached from any start in [`grid_bfs`].
+pub const UNREACHABLE: u32 = u32::MAX;
+
+/// Number of steps from the nearest start to every reachable node.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(N) -> I,
+) -> FastMap<N, u32>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = FastMap::new();
+    let mut todo = VecDeque::new();
+
+    for start in starts {
+        if distances.insert(start, 0).is_none() {
+            todo.push_back((start, 0));
+        }
+    }
+
+    while let Some((node, cost)) = todo.pop_front() {
+        for next in neighbors(node) {
+            if let Entry::Vacant(entry) = distances.entry(next) {
+                entry.insert(cost + 1);
+                todo.push_back((next, cost + 1));
+            }
+        }
+    }
+
+    distances
+}
+
+/// All nodes reachable from any of the starts, including the starts themselves.
+pub fn reachable<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(N) -> I,
+) -> FastSet<N>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = FastSet::new();
+    let mut todo = Vec::new();
+
+    for start in starts {
+        if seen.insert(start) {
+            todo.push(start);
+        }
+    }
+
+    while let Some(node) = todo.pop() {
+        for next in neighbors(node) {
+            if seen.insert(next) {
+                todo.push(next);
+            }
+        }
+    }
+
+    seen
+}
+
+/// Connected component label of each node, and the number of nodes in each component.
+pub struct Components<L> {
+    pub labels: L,
+    pub sizes: Vec<usize>,
+}
+
+/// Labels every node with the index of its connected component. Labels start at 0 in the order
+/// components are first seen in `nodes`. Edges are assumed to be symmetric.
+pub fn components<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(N) -> I,
+) -> Components<FastMap<N, u32>>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut labels = FastMap::new();
+    let mut sizes = Vec::new();
+    let mut todo = Vec::new();
+
+    for start in nodes {
+        if labels.contains_key(&start) {
+            continue;
+        }
+
+        let label = sizes.len() as u32;
+        let mut size = 1;
+        labels.insert(start, label);
+        todo.push(start);
+
+        while let Some(node) = todo.pop() {
+            for next in neighbors(node) {
+                if let Entry::Vacant(entry) = labels.entry(next) {
+                    entry.insert(label);
+                    size += 1;
+                    todo.push(next);
+                }
+            }
+        }
+
+        sizes.push(size);
+    }
+
+    Components { labels, sizes }
+}
+
+/// Number of orthogonal steps from the nearest start to every cell, or [`UNREACHABLE`].
+/// `can_move(from, to)` decides whether a step between two adjacent cells is allowed.
+pub fn grid_bfs<T>(
+    grid: &Grid<T>,
+    starts: impl IntoIterator<Item = Point>,
+    can_move: impl Fn(&T, &T) -> bool,
+) -> Grid<u32> {
+    let mut distances = grid.same_size_with(UNREACHABLE);
+    let mut todo = VecDeque::new();
+
+    for start in starts {
+        if distances[start] == UNREACHABLE {
+            distances[start] = 0;
+            todo.push_back(start);
+        }
+    }
+
+    while let Some(point) = todo.pop_front() {
+        let cost = distances[point] + 1;
+        for (next, cell) in grid.neighbors4(point) {
+            if distances[next] == UNREACHABLE && can_move(&grid[point], cell) {
+                distances[next] = cost;
+                todo.push_back(next);
+            }
+        }
+    }
+
+    distances
+}
+
+/// Replaces every cell orthogonally connected to `start` for which `matches` holds with `value`,
+/// returning how many cells were filled. Nothing is filled if `start` itself doesn't match.
+pub fn grid_flood_fill<T: Copy>(
+    grid: &mut Grid<T>,
+    start: Point,
+    matches: impl Fn(&T) -> bool,
+    value: T,
+) -> usize {
+    if !grid.get(start).is_some_and(&matches) {
+        return 0;
+    }
+
+    // Tracks filled cells separately, as `value` itself may still match.
+    let mut seen = grid.same_size_with(false);
+    let mut filled = 1;
+    let mut todo = vec![start];
+    seen[start] = true;
+    grid[start] = value;
+
+    while let Some(point) = todo.pop() {
+        for next in ORTHOGONAL.map(|o| point + o) {
+            if grid.get(next).is_some_and(&matches) && !seen[next] {
+                seen[next] = true;
+                grid[next] = value;
+                filled += 1;
+                todo.push(next);
+            }
+        }
+    }
+
+    filled
+}
+
+/// Labels orthogonally connected regions of the grid where `same(a, b)` holds between adjacent
+/// cells. Labels are assigned left to right and top to bottom.
+pub fn grid_components<T>(
+    grid: &Grid<T>,
+    same: impl Fn(&T, &T) -> bool,
+) -> Components<Grid<u32>> {
+    const UNLABELLED: u32 = u32::MAX;
+
+    let mut labels = grid.same_size_with(UNLABELLED);
+    let mut sizes = Vec::new();
+    let mut todo = Vec::new();
+
+    for y in 0..grid.height {
+        for x in 0..grid.width {
+            let start = Point::new(x, y);
+            if labels[start] != UNLABELLED {
+                continue;
+            }
+
+            let label = sizes.len() as u32;
+            let mut size = 1;
+            labels[start] = label;
+            todo.push(start);
+
+            while let Some(point) = todo.pop() {
+                for (next, cell) in grid.neighbors4(point) {
+                    if labels[next] == UNLABELLED && same(&grid[point], cell) {
+                        labels[next] = label;
+                        size += 1;
+                        todo.push(next);
+                    }
+                }
+            }
+
+            sizes.push(size);
+        }
+    }
+
+    Components { labels, sizes }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid_searches() {
+        let mut grid = Grid::parse("..#.\n.##.\n....\n##.#");
+        let open = |_: &u8, &to: &u8| to == b'.';
+
+        let distances = grid_bfs(&grid, [ORIGIN], open);
+        assert_eq!(distances[Point::new(3, 0)], 7);
+        assert_eq!(distances[Point::new(2, 3)], 5);
+        assert_eq!(distances[Point::new(2, 0)], UNREACHABLE);
+        let distances = grid_bfs(&grid, [ORIGIN, Point::new(3, 0)], open);
+        assert_eq!(distances[Point::new(3, 2)], 2);
+
+        let generic = bfs([ORIGIN], |p| {
+            grid.neighbors4(p).filter(|&(_, &c)| c == b'.').map(|(n, _)| n).collect::<Vec<_>>()
+        });
+        assert_eq!(generic[&Point::new(3, 0)], 7);
+        assert_eq!(generic.len(), 10);
+
+        let Components { labels, sizes } = grid_components(&grid, |a, b| a == b);
+        assert_eq!(sizes, [10, 3, 2, 1]);
+        assert_eq!(labels[Point::new(1, 1)], 1);
+        assert_eq!(labels[Point::new(3, 3)], 3);
+
+        assert_eq!(grid_flood_fill(&mut grid, Point::new(1, 1), |&c| c == b'#', b'x'), 3);
+        assert_eq!(grid, Grid::parse("..x.\n.xx.\n....\n##.#"));
+        assert_eq!(grid_flood_fill(&mut grid, ORIGIN, |&c| c == b'#', b'x'), 0);
+        // The fill value matches too, so filled cells must not be visited again.
+        assert_eq!(grid_flood_fill(&mut grid, ORIGIN, |&c| c != b'#', b'x'), 13);
+        assert_eq!(grid, Grid::parse("xxxx\nxxxx\nxxxx\n##x#"));
+    }
+
+    #[test]
+    fn graph_searches() {
+        // Links each number to its double, giving chains like 3, 6, 12.
+        let edges = |n: u32| {
+            let half = n.is_multiple_of(2).then_some(n / 2);
+            let double = (n * 2 < 20).then_some(n * 2);
+            half.into_iter().chain(double)
+        };
+        assert_eq!(reachable([6], edges), FastSet::build([3, 6, 12]));
+
+        let Components { labels, sizes } = components(1..20, edges);
+        assert_eq!(sizes, [5, 3, 2, 2, 2, 1, 1, 1, 1, 1]);
+        assert_eq!(labels[&12], labels[&3]);
+    }
+}
@@ -0,0 +1,157 @@
+//! Splits puzzle input into blocks separated by one or more blank lines.
+//!
+//! Many Advent of Code inputs are made of several sections, for example a list of rules followed
+//! by a list of queries, or numbered shapes followed by regions:
+//!
+//! ```none
+//!   0:
+//!   ###
+//!   ##.
+//!
+//!   4x4: 0 0 0 0 2 0
+//! ```
+//!
+//! [`sections`] yields each block together with the line number it starts on. A first line
+//! ending in `:` is treated as a header and returned as the section [`name`], the remaining lines
+//! become the section [`text`]. Lines containing only whitespace count as blank, so both `\n`
+//! and `\r\n` line endings work.
+//!
+//! [`sections`]: SectionOps::sections
+//! [`name`]: Section::name
+//! [`text`]: Section::text
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Section<'a> {
+    /// Header of the section without the trailing `:`, if the first line is a header.
+    pub name: Option<&'a str>,
+    /// Line number (starting from 1) of the first line of the section, including any header.
+    pub line: usize,
+    /// Body of the section, without header and without trailing line ending.
+    pub text: &'a str,
+}
+
+impl<'a> Section<'a> {
+    #[inline]
+    pub fn lines(&self) -> std::str::Lines<'a> {
+        self.text.lines()
+    }
+}
+
+pub struct Sections<'a> {
+    rest: &'a str,
+    line: usize,
+}
+
+pub trait SectionOps {
+    fn sections(&self) -> Sections<'_>;
+}
+
+impl<S: AsRef<str>> SectionOps for S {
+    fn sections(&self) -> Sections<'_> {
+        Sections { rest: self.as_ref(), line: 1 }
+    }
+}
+
+impl<'a> Sections<'a> {
+    /// Returns exactly `N` sections, or an error describing the sections that were found.
+    pub fn exactly<const N: usize>(self) -> Result<[Section<'a>; N], SectionError> {
+        let sections: Vec<_> = self.collect();
+        sections.try_into().map_err(|found: Vec<Section<'_>>| SectionError {
+            expected: N,
+            found: found.iter().map(|s| s.line).collect(),
+        })
+    }
+
+    /// Byte length of the next line including its line ending.
+    #[inline]
+    fn next_line_len(&self, from: usize) -> usize {
+        self.rest[from..].find('\n').map_or(self.rest.len() - from, |i| i + 1)
+    }
+}
+
+impl<'a> Iterator for Sections<'a> {
+    type Item = Section<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Skip leading blank lines
+        loop {
+            if self.rest.is_empty() {
+                return None;
+            }
+            let len = self.next_line_len(0);
+            if !self.rest[..len].trim().is_empty() {
+                break;
+            }
+            self.rest = &self.rest[len..];
+            self.line += 1;
+        }
+
+        let line = self.line;
+        let mut end = 0;
+        while end < self.rest.len() {
+            let len = self.next_line_len(end);
+            if self.rest[end..end + len].trim().is_empty() {
+                break;
+            }
+            end += len;
+            self.line += 1;
+        }
+
+        let block = self.rest[..end].trim_end_matches(['\r', '\n']);
+        self.rest = &self.rest[end..];
+
+        let (first, body) = block.split_once('\n').unwrap_or((block, ""));
+        let section = match first.trim_end().strip_suffix(':') {
+            Some(name) => Section { name: Some(name), line, text: body },
+            None => Section { name: None, line, text: block },
+        };
+        Some(section)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SectionError {
+    pub expected: usize,
+    /// Starting line of each section that was found.
+    pub found: Vec<usize>,
+}
+
+impl fmt::Display for SectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Expected {} sections separated by blank lines, found {}",
+            self.expected,
+            self.found.len()
+        )?;
+        if !self.found.is_empty() {
+            let lines: Vec<_> = self.found.iter().map(usize::to_string).collect();
+            write!(f, " starting on lines {}", lines.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sections() {
+        let input = "\n0:\r\n##\r\n#.\r\n\r\n\r\n1:\n.#\n\n4x4: 0 1\n5x5: 1 0\n";
+        let [first, second, regions] = input.sections().exactly().unwrap();
+
+        assert_eq!(first, Section { name: Some("0"), line: 2, text: "##\r\n#." });
+        assert_eq!(first.lines().collect::<Vec<_>>(), ["##", "#."]);
+        assert_eq!(second, Section { name: Some("1"), line: 7, text: ".#" });
+        assert_eq!(regions, Section { name: None, line: 10, text: "4x4: 0 1\n5x5: 1 0" });
+
+        let error = input.sections().exactly::<2>().unwrap_err();
+        assert_eq!(error, SectionError { expected: 2, found: vec![2, 7, 10] });
+        assert_eq!(
+            error.to_string(),
+            "Expected 2 sections separated by blank lines, found 3 starting on lines 2, 7, 10"
+        );
+    }
+}
@@ -0,0 +1,196 @@
+//! Unbounded grid that only stores occupied cells, for simulations that grow past their starting
+//! area or where most of the area is empty.
+//!
+//! Offers the same indexing, neighbor and printing methods as [`Grid`], with the difference
+//! that neighbors only include occupied cells. The bounding box of all cells ever inserted is
+//! tracked, so the grid can be converted back into a dense [`Grid`] with [`SparseGrid::to_grid`].
+use crate::util::grid::Grid;
+use crate::util::hash::*;
+use crate::util::point::*;
+use crate::util::rect::Rect;
+use std::ops::{Index, IndexMut};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    pub cells: FastMap<Point, T>,
+    bounds: Rect,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid::new()
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        SparseGrid {
+            cells: FastMap::new(),
+            bounds: Rect::new(Point::new(i32::MAX, i32::MAX), Point::new(i32::MIN, i32::MIN)),
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Whether there is a cell at `point`.
+    #[inline]
+    pub fn contains(&self, point: Point) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    #[inline]
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.cells.get_mut(&p)
+    }
+
+    /// Stores `value` at `p`, growing the bounding box if needed, and returns the previous value.
+    #[inline]
+    pub fn insert(&mut self, p: Point, value: T) -> Option<T> {
+        self.bounds = self.bounds.union(Rect::new(p, p));
+        self.cells.insert(p, value)
+    }
+
+    /// Removes the cell at `p`. The bounding box is left unchanged.
+    #[inline]
+    pub fn remove(&mut self, p: Point) -> Option<T> {
+        self.cells.remove(&p)
+    }
+
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(&p, v)| (p, v))
+    }
+
+    #[inline]
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        self.cells.keys().copied()
+    }
+
+    /// Bounding box of every cell inserted so far, or `None` if nothing was ever inserted.
+    #[inline]
+    pub fn bounds(&self) -> Option<Rect> {
+        (!self.bounds.is_empty()).then_some(self.bounds)
+    }
+
+    #[inline]
+    pub fn width(&self) -> i32 {
+        self.bounds.width()
+    }
+
+    #[inline]
+    pub fn height(&self) -> i32 {
+        self.bounds.height()
+    }
+
+    /// Occupied neighbors of `p` at each of the `offsets`.
+    #[inline]
+    pub fn neighbors<'a>(
+        &'a self,
+        p: Point,
+        offsets: &'a [Point],
+    ) -> impl Iterator<Item = (Point, &'a T)> + 'a {
+        offsets.iter().filter_map(move |&o| self.get(p + o).map(|value| (p + o, value)))
+    }
+
+    #[inline]
+    pub fn neighbors4(&self, p: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.neighbors(p, &ORTHOGONAL)
+    }
+
+    #[inline]
+    pub fn neighbors8(&self, p: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.neighbors(p, &DIAGONAL)
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    /// Keeps only the cells of `grid` for which `keep` holds, at the same coordinates.
+    pub fn from_grid(grid: &Grid<T>, keep: impl Fn(&T) -> bool) -> Self {
+        let mut sparse = SparseGrid::new();
+        for y in 0..grid.height {
+            for x in 0..grid.width {
+                let point = Point::new(x, y);
+                if keep(&grid[point]) {
+                    sparse.insert(point, grid[point].clone());
+                }
+            }
+        }
+        sparse
+    }
+
+    /// Dense grid covering the bounding box, with empty cells set to `fill`. The top left corner
+    /// of the bounding box becomes the origin.
+    pub fn to_grid(&self, fill: T) -> Grid<T> {
+        let (width, height) = (self.width(), self.height());
+        let mut grid = Grid { width, height, bytes: vec![fill; (width * height) as usize] };
+        for (&point, value) in &self.cells {
+            grid[point - self.bounds.min] = value.clone();
+        }
+        grid
+    }
+}
+
+impl SparseGrid<u8> {
+    /// Prints the bounding box, with empty cells shown as `.`.
+    pub fn print(&self) {
+        self.to_grid(b'.').print();
+    }
+}
+
+impl<T: Clone> From<&Grid<T>> for SparseGrid<T> {
+    fn from(grid: &Grid<T>) -> Self {
+        SparseGrid::from_grid(grid, |_| true)
+    }
+}
+
+impl<T> Index<Point> for SparseGrid<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, index: Point) -> &Self::Output {
+        &self.cells[&index]
+    }
+}
+
+impl<T> IndexMut<Point> for SparseGrid<T> {
+    #[inline]
+    fn index_mut(&mut self, index: Point) -> &mut Self::Output {
+        self.cells.get_mut(&index).unwrap_or_else(|| panic!("No cell at {index:?}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sparse() {
+        let grid = Grid::parse("#.\n.#");
+        let mut sparse = SparseGrid::from_grid(&grid, |&b| b == b'#');
+        assert_eq!(sparse.len(), 2);
+        assert_eq!(sparse.neighbors8(ORIGIN).count(), 1);
+        assert_eq!(sparse.neighbors4(ORIGIN).count(), 0);
+
+        sparse.insert(Point::new(-1, 3), b'@');
+        assert_eq!(sparse.bounds(), Some(Rect::new(Point::new(-1, 0), Point::new(1, 3))));
+        assert_eq!(sparse.to_grid(b' '), Grid::parse(" # \n  #\n   \n@  "));
+
+        sparse[Point::new(1, 1)] = b'+';
+        assert_eq!(sparse.remove(ORIGIN), Some(b'#'));
+        assert_eq!(sparse.to_grid(b'.'), Grid::parse("...\n..+\n...\n@.."));
+        assert_eq!(SparseGrid::from(&grid).len(), 4);
+    }
+}